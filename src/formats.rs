@@ -1,19 +1,50 @@
 //! Supported formats. Each module here is toggled by a feature of the same name.
 
+#[cfg(feature = "bencode")]
+pub mod bencode;
 #[cfg(feature = "bincode")]
 pub mod bincode;
+#[cfg(feature = "bson")]
+pub mod bson;
 #[cfg(feature = "cbor")]
 pub mod cbor;
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "flexbuffers")]
+pub mod flexbuffers;
+#[cfg(feature = "ini")]
+pub mod ini;
 #[cfg(feature = "json")]
 pub mod json;
+#[cfg(feature = "json5")]
+pub mod json5;
 #[cfg(feature = "messagepack")]
 pub mod messagepack;
+#[cfg(feature = "pickle")]
+pub mod pickle;
+#[cfg(feature = "plist")]
+pub mod plist;
+#[cfg(feature = "postcard")]
+pub mod postcard;
+#[cfg(feature = "properties")]
+pub mod properties;
 #[cfg(feature = "ron")]
 pub mod ron;
+#[cfg(feature = "sexpr")]
+pub mod sexpr;
 #[cfg(feature = "toml")]
 pub mod toml;
+#[cfg(feature = "urlencoded")]
+pub mod urlencoded;
 #[cfg(feature = "xml")]
 pub mod xml;
+#[cfg(feature = "yaml")]
+pub mod yaml;
+
+#[cfg(any(feature = "csv", feature = "ini", feature = "properties"))]
+mod field;
+#[cfg(any(feature = "ini", feature = "properties"))]
+mod sections;
 
 #[inline(always)]
 fn map_err<T: std::error::Error + Send + Sync + 'static>(err: T) -> crate::Error {
@@ -0,0 +1,32 @@
+//! Bencode serialization/deserialization, via the [`serde_bencode`] crate.
+//!
+//! Bencode only has integers, byte strings, lists and dictionaries. Booleans are written as integers,
+//! `None` and unit values are left out, and floats can't be serialized at all.
+//! Dictionary keys are always written sorted by their raw bytes, as the specification requires,
+//! so the same value always serializes to the same bytes.
+//!
+//! [`serde_bencode`]: https://crates.io/crates/serde_bencode
+
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
+use serde::ser::Serialize;
+use serde_bencode::Deserializer;
+use std::io::{Read, Write};
+
+use crate::traits::{SerdeBytes, SerdeStream, Extension, Metadata};
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Bencode;
+
+function!(to_vec, super::map_err, serde_bencode::to_bytes);
+function!(from_slice, super::map_err, serde_bencode::from_bytes);
+function!(from_slice_seed, |t| t, |seed, data| Ok(seed.deserialize(&mut Deserializer::new(data))?));
+function!(to_writer, |t| t, |writer, value| {
+  {writer}.write_all(&to_vec(value)?)?;
+  Ok(())
+});
+function!(from_reader, |t| t, |reader| Ok(Deserialize::deserialize(&mut Deserializer::new(reader))?));
+function!(from_reader_seed, |t| t, |seed, reader| Ok(seed.deserialize(&mut Deserializer::new(reader))?));
+
+implement!(Bencode, SerdeBytes);
+implement!(Bencode, SerdeStream);
+implement!(Bencode, Metadata, ["torrent", "bencode"], ["application/x-bittorrent"]);
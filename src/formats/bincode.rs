@@ -2,24 +2,314 @@
 //!
 //! [`bincode`]: https://crates.io/crates/bincode
 
-use serde::de::{Deserialize, DeserializeOwned};
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
 use serde::ser::Serialize;
+use serde_bincode::{DefaultOptions, Options};
 use std::io::{Read, Write};
+use std::marker::PhantomData;
 
-use crate::traits::{SerdeBytes, SerdeStream};
+use crate::traits::{SerdeBytes, SerdeStream, Extension, Metadata};
 
-#[derive(Debug, Copy, Clone, Default)]
-pub struct Bincode;
+/// Bincode format options, mapping to bincode's [`Options`].
+///
+/// The free functions in this module, and `Bincode::default()`, use the same options as
+/// [`bincode::serialize`] and [`bincode::deserialize`]: little-endian fixed-size integers,
+/// trailing bytes allowed and no byte limit.
+///
+/// [`Options`]: https://docs.rs/bincode/1.3/bincode/config/trait.Options.html
+/// [`bincode::serialize`]: https://docs.rs/bincode/1.3/bincode/fn.serialize.html
+/// [`bincode::deserialize`]: https://docs.rs/bincode/1.3/bincode/fn.deserialize.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Bincode {
+  endian: Endian,
+  int_encoding: IntEncoding,
+  trailing_bytes: TrailingBytes,
+  limit: Option<u64>
+}
+
+/// The byte order integers and floats are encoded with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Endian {
+  Little,
+  Big,
+  /// The byte order of the machine this is compiled for.
+  Native
+}
+
+/// How integers are encoded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum IntEncoding {
+  /// Integers are always encoded with their full size.
+  Fixint,
+  /// Integers are encoded with a variable number of bytes, with smaller values taking up less space.
+  Varint
+}
+
+/// What happens when there is data left over after deserializing a value from a slice.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TrailingBytes {
+  /// Leftover data is ignored.
+  Allow,
+  /// Leftover data is an error.
+  Reject
+}
+
+impl Bincode {
+  /// Creates a new `Bincode` with the default options.
+  #[inline]
+  pub const fn new() -> Self {
+    Bincode {
+      endian: Endian::Little,
+      int_encoding: IntEncoding::Fixint,
+      trailing_bytes: TrailingBytes::Allow,
+      limit: None
+    }
+  }
+
+  /// Sets the byte order integers and floats are encoded with.
+  #[inline]
+  pub fn with_endian(mut self, endian: Endian) -> Self {
+    self.endian = endian;
+    self
+  }
+
+  /// Sets how integers are encoded.
+  #[inline]
+  pub fn with_int_encoding(mut self, int_encoding: IntEncoding) -> Self {
+    self.int_encoding = int_encoding;
+    self
+  }
+
+  /// Sets what happens when there is data left over after deserializing a value from a slice.
+  #[inline]
+  pub fn with_trailing_bytes(mut self, trailing_bytes: TrailingBytes) -> Self {
+    self.trailing_bytes = trailing_bytes;
+    self
+  }
+
+  /// Limits the number of bytes that may be written or read for a single value, from a slice or a reader.
+  /// This bounds the memory allocated when deserializing untrusted input.
+  #[inline]
+  pub fn with_limit(mut self, limit: u64) -> Self {
+    self.limit = Some(limit);
+    self
+  }
+
+  /// Removes the byte limit.
+  #[inline]
+  pub fn with_no_limit(mut self) -> Self {
+    self.limit = None;
+    self
+  }
+
+  /// The byte order integers and floats are encoded with.
+  #[inline]
+  pub fn endian(&self) -> Endian {
+    self.endian
+  }
+
+  /// How integers are encoded.
+  #[inline]
+  pub fn int_encoding(&self) -> IntEncoding {
+    self.int_encoding
+  }
 
-function!(to_vec, map_err, |value| serde_bincode::serialize(value));
-function!(from_slice, map_err, |data| serde_bincode::deserialize(data));
-function!(to_writer, map_err, |writer, value| serde_bincode::serialize_into(writer, value));
-function!(from_reader, map_err, |reader| serde_bincode::deserialize_from(reader));
+  /// What happens when there is data left over after deserializing a value from a slice.
+  #[inline]
+  pub fn trailing_bytes(&self) -> TrailingBytes {
+    self.trailing_bytes
+  }
 
-implement!(Bincode, SerdeBytes);
-implement!(Bincode, SerdeStream);
+  /// The number of bytes that may be written or read for a single value, if limited.
+  #[inline]
+  pub fn limit(&self) -> Option<u64> {
+    self.limit
+  }
+
+  fn serialize<T>(&self, value: &T) -> serde_bincode::Result<Vec<u8>>
+  where T: Serialize {
+    self.with_options(ToVec(value))
+  }
+
+  fn serialize_into<W, T>(&self, writer: W, value: &T) -> serde_bincode::Result<()>
+  where W: Write, T: Serialize {
+    self.with_options(ToWriter(writer, value))
+  }
+
+  fn deserialize_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> serde_bincode::Result<S::Value>
+  where S: DeserializeSeed<'d> {
+    // bincode ignores the limit for slices, but it can only be exceeded by slices longer than it
+    match self.limit {
+      Some(limit) if data.len() as u64 > limit => match self.trailing_bytes {
+        TrailingBytes::Allow => self.with_options(FromLimitedSlice(seed, data)),
+        TrailingBytes::Reject => Err(Box::new(serde_bincode::ErrorKind::SizeLimit))
+      },
+      _ => self.with_options(FromSlice(seed, data))
+    }
+  }
+
+  fn deserialize_from_seed<R, S, V>(&self, seed: S, reader: R) -> serde_bincode::Result<V>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    self.with_options(FromReader(seed, reader))
+  }
+
+  /// Runs an operation with the [`Options`] described by these options.
+  /// Each of bincode's options is a separate type, so every combination has to be spelled out.
+  ///
+  /// [`Options`]: https://docs.rs/bincode/1.3/bincode/config/trait.Options.html
+  fn with_options<F: WithOptions>(&self, f: F) -> F::Output {
+    let options = DefaultOptions::new();
+    match self.endian {
+      Endian::Little => self.with_int_encoding_options(options.with_little_endian(), f),
+      Endian::Big => self.with_int_encoding_options(options.with_big_endian(), f),
+      Endian::Native => self.with_int_encoding_options(options.with_native_endian(), f)
+    }
+  }
+
+  fn with_int_encoding_options<O: Options, F: WithOptions>(&self, options: O, f: F) -> F::Output {
+    match self.int_encoding {
+      IntEncoding::Fixint => self.with_trailing_bytes_options(options.with_fixint_encoding(), f),
+      IntEncoding::Varint => self.with_trailing_bytes_options(options.with_varint_encoding(), f)
+    }
+  }
+
+  fn with_trailing_bytes_options<O: Options, F: WithOptions>(&self, options: O, f: F) -> F::Output {
+    match self.trailing_bytes {
+      TrailingBytes::Allow => self.with_limit_options(options.allow_trailing_bytes(), f),
+      TrailingBytes::Reject => self.with_limit_options(options.reject_trailing_bytes(), f)
+    }
+  }
+
+  fn with_limit_options<O: Options, F: WithOptions>(&self, options: O, f: F) -> F::Output {
+    match self.limit {
+      Some(limit) => f.call(options.with_limit(limit)),
+      None => f.call(options.with_no_limit())
+    }
+  }
+}
+
+impl Default for Bincode {
+  #[inline]
+  fn default() -> Self {
+    Bincode::new()
+  }
+}
+
+function!(to_vec, map_err, |value| Bincode::new().serialize(value));
+function!(from_slice, map_err, |data| Bincode::new().deserialize_seed(PhantomData, data));
+function!(from_slice_seed, map_err, |seed, data| Bincode::new().deserialize_seed(seed, data));
+function!(to_writer, map_err, |writer, value| Bincode::new().serialize_into(writer, value));
+function!(from_reader, map_err, |reader| Bincode::new().deserialize_from_seed(PhantomData, reader));
+function!(from_reader_seed, map_err, |seed, reader| Bincode::new().deserialize_from_seed(seed, reader));
+
+impl SerdeBytes for Bincode {
+  #[inline]
+  fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    self.serialize(value).map_err(map_err)
+  }
+
+  #[inline]
+  fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    self.deserialize_seed(PhantomData, data).map_err(map_err)
+  }
+
+  #[inline]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    self.deserialize_seed(seed, data).map_err(map_err)
+  }
+}
+
+impl SerdeStream for Bincode {
+  #[inline]
+  fn to_writer<W, T>(&self, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    self.serialize_into(writer, value).map_err(map_err)
+  }
+
+  #[inline]
+  fn from_reader<R, T>(&self, reader: R) -> Result<T, crate::Error>
+  where R: Read, T: DeserializeOwned {
+    self.deserialize_from_seed(PhantomData, reader).map_err(map_err)
+  }
+
+  #[inline]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    self.deserialize_from_seed(seed, reader).map_err(map_err)
+  }
+}
+
+implement!(Bincode, Metadata, ["bin", "bincode"], ["application/x-bincode"]);
 
 #[inline(always)]
 fn map_err(err: serde_bincode::Error) -> crate::Error {
-  Box::new(*err)
+  err
+}
+
+/// An operation that is generic over bincode's [`Options`].
+///
+/// [`Options`]: https://docs.rs/bincode/1.3/bincode/config/trait.Options.html
+trait WithOptions {
+  type Output;
+
+  fn call<O: Options>(self, options: O) -> Self::Output;
+}
+
+struct ToVec<'a, T>(&'a T);
+
+impl<'a, T: Serialize> WithOptions for ToVec<'a, T> {
+  type Output = serde_bincode::Result<Vec<u8>>;
+
+  #[inline]
+  fn call<O: Options>(self, options: O) -> Self::Output {
+    options.serialize(self.0)
+  }
+}
+
+struct ToWriter<'a, W, T>(W, &'a T);
+
+impl<'a, W: Write, T: Serialize> WithOptions for ToWriter<'a, W, T> {
+  type Output = serde_bincode::Result<()>;
+
+  #[inline]
+  fn call<O: Options>(self, options: O) -> Self::Output {
+    options.serialize_into(self.0, self.1)
+  }
+}
+
+struct FromSlice<'d, S>(S, &'d [u8]);
+
+impl<'d, S: DeserializeSeed<'d>> WithOptions for FromSlice<'d, S> {
+  type Output = serde_bincode::Result<S::Value>;
+
+  #[inline]
+  fn call<O: Options>(self, options: O) -> Self::Output {
+    options.deserialize_seed(self.0, self.1)
+  }
+}
+
+struct FromLimitedSlice<'d, S>(S, &'d [u8]);
+
+impl<'d, S: DeserializeSeed<'d>> WithOptions for FromLimitedSlice<'d, S> {
+  type Output = serde_bincode::Result<S::Value>;
+
+  #[inline]
+  fn call<O: Options>(self, options: O) -> Self::Output {
+    self.0.deserialize(&mut serde_bincode::Deserializer::from_slice(self.1, options))
+  }
+}
+
+struct FromReader<S, R>(S, R);
+
+impl<S, R, V> WithOptions for FromReader<S, R>
+where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+  type Output = serde_bincode::Result<V>;
+
+  #[inline]
+  fn call<O: Options>(self, options: O) -> Self::Output {
+    options.deserialize_from_seed(self.0, self.1)
+  }
 }
@@ -0,0 +1,65 @@
+//! BSON serialization/deserialization, via the [`bson`] crate.
+//!
+//! BSON can only represent documents at the top level, so only values that serialize as maps or structs
+//! can be serialized. Attempting to serialize anything else fails with a [`NotADocument`] error.
+//!
+//! [`bson`]: https://crates.io/crates/bson
+//! [`NotADocument`]: ./struct.NotADocument.html
+
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
+use serde::ser::Serialize;
+use serde_bson::spec::ElementType;
+use serde_bson::Document;
+use std::fmt::{self, Display};
+use std::io::{Read, Write};
+
+use crate::traits::{SerdeBytes, SerdeStream, Extension, Metadata};
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Bson;
+
+function!(to_vec, |t| t, |value| serde_bson::to_vec(value).map_err(|error| not_a_document(value).unwrap_or_else(|| Box::new(error))));
+function!(from_slice, super::map_err, serde_bson::from_slice);
+function!(from_slice_seed, super::map_err, |seed, data| seed.deserialize(serde_bson::Deserializer::new(Document::from_reader(data)?.into())));
+function!(to_writer, |t| t, |writer, value| {
+  {writer}.write_all(&to_vec(value)?)?;
+  Ok(())
+});
+function!(from_reader, super::map_err, serde_bson::from_reader);
+function!(from_reader_seed, super::map_err, |seed, reader| seed.deserialize(serde_bson::Deserializer::new(Document::from_reader(reader)?.into())));
+
+implement!(Bson, SerdeBytes);
+implement!(Bson, SerdeStream);
+implement!(Bson, Metadata, ["bson"], ["application/bson"]);
+
+/// An error returned when serializing a value that is not a document, such as a number or a sequence.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct NotADocument {
+  found: ElementType
+}
+
+impl NotADocument {
+  /// The BSON type the value would have been serialized as.
+  #[inline]
+  pub fn found(&self) -> ElementType {
+    self.found
+  }
+}
+
+impl Display for NotADocument {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "BSON can only serialize a document at the top level, found {:?}", self.found)
+  }
+}
+
+impl std::error::Error for NotADocument {}
+
+/// Explains why serializing a value failed, if it was because it is not a document.
+#[inline]
+fn not_a_document<T: Serialize + ?Sized>(value: &T) -> Option<crate::Error> {
+  match serde_bson::to_bson(value).ok()?.element_type() {
+    ElementType::EmbeddedDocument => None,
+    found => Some(Box::new(NotADocument { found }))
+  }
+}
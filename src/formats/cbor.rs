@@ -2,19 +2,199 @@
 //!
 //! [`serde_cbor`]: https://crates.io/crates/serde_cbor
 
-use serde::de::{Deserialize, DeserializeOwned};
-use serde::ser::Serialize;
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
+use serde::ser::{Error as _, Serialize, SerializeMap, Serializer};
+use serde_cbor::ser::IoWrite;
+use serde_cbor::tags::Tagged;
+use serde_cbor::Value;
 use std::io::{Read, Write};
 
-use crate::traits::{SerdeBytes, SerdeStream};
+use crate::traits::{SerdeBytes, SerdeStream, Extension, Metadata};
 
-#[derive(Debug, Copy, Clone, Default)]
-pub struct Cbor;
+/// CBOR format options, used when serializing.
+///
+/// The free functions in this module, and `Cbor::default()`, use none of these options.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Cbor {
+  packed: bool,
+  self_describe: bool,
+  canonical: bool
+}
+
+impl Cbor {
+  /// Creates a new `Cbor` with the default options.
+  #[inline]
+  pub const fn new() -> Self {
+    Cbor {
+      packed: false,
+      self_describe: false,
+      canonical: false
+    }
+  }
+
+  /// Sets whether struct fields and unit enum variants are identified by their index instead of their name.
+  #[inline]
+  pub fn with_packed(mut self, packed: bool) -> Self {
+    self.packed = packed;
+    self
+  }
+
+  /// Sets whether the output starts with the self-describe tag (55799),
+  /// which lets other tools recognize the data as CBOR.
+  #[inline]
+  pub fn with_self_describe(mut self, self_describe: bool) -> Self {
+    self.self_describe = self_describe;
+    self
+  }
+
+  /// Sets whether the output uses the deterministic encoding from [RFC 8949 section 4.2],
+  /// so that equal values always serialize to the same bytes.
+  /// This buffers the whole value in memory while map keys are sorted.
+  ///
+  /// [RFC 8949 section 4.2]: https://www.rfc-editor.org/rfc/rfc8949.html#section-4.2
+  #[inline]
+  pub fn with_canonical(mut self, canonical: bool) -> Self {
+    self.canonical = canonical;
+    self
+  }
+
+  /// Whether struct fields and unit enum variants are identified by their index instead of their name.
+  #[inline]
+  pub fn packed(&self) -> bool {
+    self.packed
+  }
+
+  /// Whether the output starts with the self-describe tag (55799).
+  #[inline]
+  pub fn self_describe(&self) -> bool {
+    self.self_describe
+  }
+
+  /// Whether the output uses the deterministic encoding from RFC 8949.
+  #[inline]
+  pub fn canonical(&self) -> bool {
+    self.canonical
+  }
+
+  fn serialize<W, T>(&self, writer: W, value: &T) -> serde_cbor::Result<()>
+  where W: Write, T: Serialize {
+    let mut serializer = self.serializer(IoWrite::new(writer));
+    if self.self_describe {
+      serializer.self_describe()?;
+    }
+
+    if self.canonical {
+      let mut data = self.serializer(Vec::with_capacity(128));
+      value.serialize(&mut data)?;
+      let value = serde_cbor::from_slice::<Value>(&data.into_inner())?;
+      Canonical(&value).serialize(&mut serializer)
+    } else {
+      value.serialize(&mut serializer)
+    }
+  }
+
+  #[inline]
+  fn serializer<W>(&self, writer: W) -> serde_cbor::Serializer<W>
+  where W: serde_cbor::ser::Write {
+    let serializer = serde_cbor::Serializer::new(writer);
+    if self.packed { serializer.packed_format() } else { serializer }
+  }
+}
+
+impl Default for Cbor {
+  #[inline]
+  fn default() -> Self {
+    Cbor::new()
+  }
+}
 
 function!(to_vec, super::map_err, serde_cbor::to_vec);
 function!(from_slice, super::map_err, serde_cbor::from_slice);
+function!(from_slice_seed, super::map_err, |seed, data| deserialize_seed(seed, serde_cbor::Deserializer::from_slice(data)));
 function!(to_writer, super::map_err, serde_cbor::to_writer);
 function!(from_reader, super::map_err, serde_cbor::from_reader);
+function!(from_reader_seed, super::map_err, |seed, reader| deserialize_seed(seed, serde_cbor::Deserializer::from_reader(reader)));
+
+impl SerdeBytes for Cbor {
+  #[inline]
+  fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    let mut data = Vec::with_capacity(128);
+    self.serialize(&mut data, value).map_err(super::map_err)?;
+    Ok(data)
+  }
+
+  #[inline]
+  fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_slice(data)
+  }
+
+  #[inline]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_slice_seed(seed, data)
+  }
+}
+
+impl SerdeStream for Cbor {
+  #[inline]
+  fn to_writer<W, T>(&self, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    self.serialize(writer, value).map_err(super::map_err)
+  }
+
+  #[inline]
+  fn from_reader<R, T>(&self, reader: R) -> Result<T, crate::Error>
+  where R: Read, T: DeserializeOwned {
+    from_reader(reader)
+  }
+
+  #[inline]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    from_reader_seed(seed, reader)
+  }
+}
+
+implement!(Cbor, Metadata, ["cbor"], ["application/cbor"]);
+
+#[inline]
+fn deserialize_seed<'d, R, S>(seed: S, mut deserializer: serde_cbor::Deserializer<R>) -> serde_cbor::Result<S::Value>
+where R: serde_cbor::de::Read<'d>, S: DeserializeSeed<'d> {
+  let value = seed.deserialize(&mut deserializer)?;
+  deserializer.end()?;
+  Ok(value)
+}
+
+/// Serializes a [`Value`] with its map keys sorted by their encoded bytes, as RFC 8949 requires.
+/// The rest of the deterministic encoding, such as using the shortest form of every integer,
+/// float and length, is already how `serde_cbor` writes values with a known length.
+///
+/// [`Value`]: https://docs.rs/serde_cbor/0.11/serde_cbor/enum.Value.html
+struct Canonical<'a>(&'a Value);
+
+impl<'a> Serialize for Canonical<'a> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where S: Serializer {
+    match self.0 {
+      Value::Array(values) => serializer.collect_seq(values.iter().map(Canonical)),
+      Value::Map(entries) => {
+        let mut entries = entries.iter()
+          .map(|(key, value)| Ok((serde_cbor::to_vec(&Canonical(key))?, key, value)))
+          .collect::<serde_cbor::Result<Vec<_>>>()
+          .map_err(S::Error::custom)?;
+        entries.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for (_, key, value) in entries {
+          map.serialize_entry(&Canonical(key), &Canonical(value))?;
+        }
 
-implement!(Cbor, SerdeBytes);
-implement!(Cbor, SerdeStream);
+        map.end()
+      },
+      Value::Tag(tag, value) => Tagged::new(Some(*tag), Canonical(value)).serialize(serializer),
+      value => value.serialize(serializer)
+    }
+  }
+}
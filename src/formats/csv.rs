@@ -0,0 +1,563 @@
+//! CSV serialization/deserialization, via the [`csv`] crate.
+//!
+//! CSV data is a sequence of records, so only sequences can be serialized, and data
+//! can only be deserialized into sequences such as `Vec<T>`, with each record becoming one element.
+//! Records can also be read one at a time with [`records`] or [`Csv::records`].
+//!
+//! Fields are parsed as whatever type is requested when deserializing.
+//! Fields whose type is not known ahead of time, such as in a `serde_json::Value` or a `#[serde(flatten)]` map, are strings.
+//!
+//! [`csv`]: https://crates.io/crates/csv
+//! [`records`]: ./fn.records.html
+//! [`Csv::records`]: ./struct.Csv.html#method.records
+
+use serde::de::{self, Deserialize, DeserializeOwned, DeserializeSeed, Deserializer, Error as _, Visitor};
+use serde::de::value::{Error as DeError, MapDeserializer, SeqDeserializer};
+use serde::ser::{self, Impossible, Serialize, Serializer};
+use serde_csv::StringRecord;
+use std::fmt;
+use std::io::{Read, Write};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+use super::field::FieldDeserializer;
+use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension, Metadata};
+
+/// CSV format options.
+///
+/// The free functions in this module, and `Csv::default()`, use commas as delimiters,
+/// write and expect a header row, and only quote fields when necessary.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Csv {
+  delimiter: u8,
+  headers: bool,
+  quoting: Quoting
+}
+
+/// When fields are quoted when serializing CSV.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Quoting {
+  /// Only quote fields that contain a delimiter, quote or line break.
+  Necessary,
+  /// Quote every field.
+  Always,
+  /// Quote every field that is not a number, as well as those that need it.
+  NonNumeric,
+  /// Never quote fields, even if that produces invalid CSV.
+  Never
+}
+
+impl Csv {
+  /// Creates a new `Csv` with the default options.
+  #[inline]
+  pub const fn new() -> Self {
+    Csv {
+      delimiter: b',',
+      headers: true,
+      quoting: Quoting::Necessary
+    }
+  }
+
+  /// Sets the byte separating fields, such as `b'\t'` for tab-separated values.
+  #[inline]
+  pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+    self.delimiter = delimiter;
+    self
+  }
+
+  /// Sets whether the first row is a header row naming each field.
+  ///
+  /// With a header row, records are deserialized by field name. Without one,
+  /// they are deserialized by position, and struct field names are not written.
+  #[inline]
+  pub fn with_headers(mut self, headers: bool) -> Self {
+    self.headers = headers;
+    self
+  }
+
+  /// Sets when fields are quoted when serializing.
+  #[inline]
+  pub fn with_quoting(mut self, quoting: Quoting) -> Self {
+    self.quoting = quoting;
+    self
+  }
+
+  /// The byte separating fields.
+  #[inline]
+  pub fn delimiter(&self) -> u8 {
+    self.delimiter
+  }
+
+  /// Whether the first row is a header row naming each field.
+  #[inline]
+  pub fn headers(&self) -> bool {
+    self.headers
+  }
+
+  /// When fields are quoted when serializing.
+  #[inline]
+  pub fn quoting(&self) -> Quoting {
+    self.quoting
+  }
+
+  /// Returns an iterator deserializing one record at a time from a reader,
+  /// without reading the whole input ahead of time.
+  #[inline]
+  pub fn records<R, T>(&self, reader: R) -> Records<R, T>
+  where R: Read, T: DeserializeOwned {
+    let records = RecordsDeserializer { reader: self.reader(reader), headers: None, record: StringRecord::new() };
+    Records { records, started: false, finished: false, marker: PhantomData }
+  }
+
+  fn reader<R: Read>(&self, reader: R) -> serde_csv::Reader<R> {
+    serde_csv::ReaderBuilder::new()
+      .delimiter(self.delimiter)
+      .has_headers(self.headers)
+      .from_reader(reader)
+  }
+
+  fn serialize<W, T>(&self, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    let quote_style = match self.quoting {
+      Quoting::Necessary => serde_csv::QuoteStyle::Necessary,
+      Quoting::Always => serde_csv::QuoteStyle::Always,
+      Quoting::NonNumeric => serde_csv::QuoteStyle::NonNumeric,
+      Quoting::Never => serde_csv::QuoteStyle::Never
+    };
+
+    let mut writer = serde_csv::WriterBuilder::new()
+      .delimiter(self.delimiter)
+      .has_headers(self.headers)
+      .quote_style(quote_style)
+      .from_writer(writer);
+    value.serialize(RecordsSerializer(&mut writer)).map_err(super::map_err)?;
+    writer.flush()?;
+    Ok(())
+  }
+
+  fn deserialize<'d, R, S>(&self, seed: S, reader: R) -> Result<S::Value, crate::Error>
+  where R: Read, S: DeserializeSeed<'d> {
+    let mut reader = self.reader(reader);
+    let headers = if self.headers { Some(reader.headers()?.clone()) } else { None };
+    let records = RecordsDeserializer { reader, headers, record: StringRecord::new() };
+    Ok(seed.deserialize(records)?)
+  }
+}
+
+impl Default for Csv {
+  #[inline]
+  fn default() -> Self {
+    Csv::new()
+  }
+}
+
+/// Returns an iterator deserializing one record at a time from a reader, with the default options.
+#[inline]
+pub fn records<R, T>(reader: R) -> Records<R, T>
+where R: Read, T: DeserializeOwned {
+  Csv::new().records(reader)
+}
+
+function!(to_string, |t| t, |value| Csv::new().to_string(value));
+function!(from_str, |t| t, |data| Csv::new().from_str(data));
+function!(from_str_seed, |t| t, |seed, data| Csv::new().from_str_seed(seed, data));
+function!(to_vec, |t| t, |value| Csv::new().to_vec(value));
+function!(from_slice, |t| t, |data| Csv::new().from_slice(data));
+function!(from_slice_seed, |t| t, |seed, data| Csv::new().from_slice_seed(seed, data));
+function!(to_writer, |t| t, |writer, value| Csv::new().to_writer(writer, value));
+function!(from_reader, |t| t, |reader| Csv::new().from_reader(reader));
+function!(from_reader_seed, |t| t, |seed, reader| Csv::new().from_reader_seed(seed, reader));
+
+impl SerdeText for Csv {
+  #[inline]
+  fn to_string<T>(&self, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    Ok(String::from_utf8(self.to_vec(value)?)?)
+  }
+
+  #[inline]
+  fn from_str<'d, T>(&self, data: &'d str) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    self.deserialize(std::marker::PhantomData, data.as_bytes())
+  }
+
+  #[inline]
+  fn from_str_seed<'d, S>(&self, seed: S, data: &'d str) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    self.deserialize(seed, data.as_bytes())
+  }
+}
+
+impl SerdeBytes for Csv {
+  #[inline]
+  fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    let mut data = Vec::with_capacity(128);
+    self.serialize(&mut data, value)?;
+    Ok(data)
+  }
+
+  #[inline]
+  fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    self.deserialize(std::marker::PhantomData, data)
+  }
+
+  #[inline]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    self.deserialize(seed, data)
+  }
+}
+
+impl SerdeStream for Csv {
+  #[inline]
+  fn to_writer<W, T>(&self, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    self.serialize(writer, value)
+  }
+
+  #[inline]
+  fn from_reader<R, T>(&self, reader: R) -> Result<T, crate::Error>
+  where R: Read, T: DeserializeOwned {
+    self.deserialize(std::marker::PhantomData, reader)
+  }
+
+  #[inline]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    self.deserialize(seed, reader)
+  }
+}
+
+implement!(Csv, Metadata, ["csv"], ["text/csv"]);
+
+/// An iterator over the records read from a reader, created by [`records`] or [`Csv::records`].
+///
+/// [`records`]: ./fn.records.html
+/// [`Csv::records`]: ./struct.Csv.html#method.records
+pub struct Records<R, T> {
+  records: RecordsDeserializer<R>,
+  started: bool,
+  finished: bool,
+  marker: PhantomData<T>
+}
+
+impl<R, T> Iterator for Records<R, T>
+where R: Read, T: DeserializeOwned {
+  type Item = Result<T, crate::Error>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.finished {
+      return None;
+    }
+
+    // the header row is read along with the first record, so that creating the iterator cannot fail
+    if !std::mem::replace(&mut self.started, true) && self.records.reader.has_headers() {
+      match self.records.reader.headers() {
+        Ok(headers) => self.records.headers = Some(headers.clone()),
+        Err(error) => {
+          self.finished = true;
+          return Some(Err(super::map_err(error)));
+        }
+      }
+    }
+
+    match de::SeqAccess::next_element(&mut self.records) {
+      Ok(Some(record)) => Some(Ok(record)),
+      Ok(None) => {
+        self.finished = true;
+        None
+      },
+      Err(error) => Some(Err(super::map_err(error)))
+    }
+  }
+}
+
+impl<R, T> FusedIterator for Records<R, T>
+where R: Read, T: DeserializeOwned {}
+
+impl<R, T> fmt::Debug for Records<R, T> {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Records").finish_non_exhaustive()
+  }
+}
+
+/// Serializes each element of a sequence as one record.
+struct RecordsSerializer<'a, W: Write>(&'a mut serde_csv::Writer<W>);
+
+macro_rules! serialize_not_a_sequence {
+  ($($method:ident($($arg:ident: $type:ty),*) -> $ok:ty;)*) => {
+    $(
+      #[inline]
+      fn $method(self, $(_: $type),*) -> Result<$ok, Self::Error> {
+        Err(not_a_sequence())
+      }
+    )*
+  };
+}
+
+impl<'a, W: Write> Serializer for RecordsSerializer<'a, W> {
+  type Ok = ();
+  type Error = serde_csv::Error;
+  type SerializeSeq = Self;
+  type SerializeTuple = Self;
+  type SerializeTupleStruct = Impossible<(), Self::Error>;
+  type SerializeTupleVariant = Impossible<(), Self::Error>;
+  type SerializeMap = Impossible<(), Self::Error>;
+  type SerializeStruct = Impossible<(), Self::Error>;
+  type SerializeStructVariant = Impossible<(), Self::Error>;
+
+  #[inline]
+  fn serialize_seq(self, _: Option<usize>) -> Result<Self, Self::Error> {
+    Ok(self)
+  }
+
+  #[inline]
+  fn serialize_tuple(self, _: usize) -> Result<Self, Self::Error> {
+    Ok(self)
+  }
+
+  #[inline]
+  fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<(), Self::Error>
+  where T: Serialize + ?Sized {
+    value.serialize(self)
+  }
+
+  #[inline]
+  fn serialize_some<T>(self, value: &T) -> Result<(), Self::Error>
+  where T: Serialize + ?Sized {
+    value.serialize(self)
+  }
+
+  #[inline]
+  fn serialize_none(self) -> Result<(), Self::Error> {
+    Ok(())
+  }
+
+  serialize_not_a_sequence! {
+    serialize_bool(v: bool) -> ();
+    serialize_i8(v: i8) -> ();
+    serialize_i16(v: i16) -> ();
+    serialize_i32(v: i32) -> ();
+    serialize_i64(v: i64) -> ();
+    serialize_u8(v: u8) -> ();
+    serialize_u16(v: u16) -> ();
+    serialize_u32(v: u32) -> ();
+    serialize_u64(v: u64) -> ();
+    serialize_f32(v: f32) -> ();
+    serialize_f64(v: f64) -> ();
+    serialize_char(v: char) -> ();
+    serialize_str(v: &str) -> ();
+    serialize_bytes(v: &[u8]) -> ();
+    serialize_unit() -> ();
+    serialize_unit_struct(name: &'static str) -> ();
+    serialize_unit_variant(name: &'static str, index: u32, variant: &'static str) -> ();
+    serialize_tuple_struct(name: &'static str, len: usize) -> Self::SerializeTupleStruct;
+    serialize_tuple_variant(name: &'static str, index: u32, variant: &'static str, len: usize) -> Self::SerializeTupleVariant;
+    serialize_map(len: Option<usize>) -> Self::SerializeMap;
+    serialize_struct(name: &'static str, len: usize) -> Self::SerializeStruct;
+    serialize_struct_variant(name: &'static str, index: u32, variant: &'static str, len: usize) -> Self::SerializeStructVariant;
+  }
+
+  #[inline]
+  fn serialize_newtype_variant<T>(self, _: &'static str, _: u32, _: &'static str, _: &T) -> Result<(), Self::Error>
+  where T: Serialize + ?Sized {
+    Err(not_a_sequence())
+  }
+}
+
+#[inline]
+fn not_a_sequence() -> serde_csv::Error {
+  ser::Error::custom("CSV can only serialize a sequence of records")
+}
+
+impl<'a, W: Write> ser::SerializeSeq for RecordsSerializer<'a, W> {
+  type Ok = ();
+  type Error = serde_csv::Error;
+
+  #[inline]
+  fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+  where T: Serialize + ?Sized {
+    self.0.serialize(value)
+  }
+
+  #[inline]
+  fn end(self) -> Result<(), Self::Error> {
+    Ok(())
+  }
+}
+
+impl<'a, W: Write> ser::SerializeTuple for RecordsSerializer<'a, W> {
+  type Ok = ();
+  type Error = serde_csv::Error;
+
+  #[inline]
+  fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+  where T: Serialize + ?Sized {
+    self.0.serialize(value)
+  }
+
+  #[inline]
+  fn end(self) -> Result<(), Self::Error> {
+    Ok(())
+  }
+}
+
+/// Deserializes the records of a reader as a sequence.
+struct RecordsDeserializer<R> {
+  reader: serde_csv::Reader<R>,
+  headers: Option<StringRecord>,
+  record: StringRecord
+}
+
+impl<'d, R: Read> Deserializer<'d> for RecordsDeserializer<R> {
+  type Error = DeError;
+
+  #[inline]
+  fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_seq(self)
+  }
+
+  serde::forward_to_deserialize_any! {
+    <V: Visitor<'d>>
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit
+    unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+  }
+}
+
+impl<'d, R: Read> de::SeqAccess<'d> for RecordsDeserializer<R> {
+  type Error = DeError;
+
+  fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error>
+  where S: DeserializeSeed<'d> {
+    if !self.reader.read_record(&mut self.record).map_err(DeError::custom)? {
+      return Ok(None);
+    }
+
+    let record = RecordDeserializer { record: &self.record, headers: self.headers.as_ref() };
+    seed.deserialize(record).map(Some).map_err(|error| match self.record.position() {
+      Some(position) => DeError::custom(format_args!("{} at line {}", error, position.line())),
+      None => error
+    })
+  }
+}
+
+/// Deserializes a single record, as a map if there is a header row, or as a sequence otherwise.
+/// Records with a single field can also be deserialized as that field.
+struct RecordDeserializer<'a> {
+  record: &'a StringRecord,
+  headers: Option<&'a StringRecord>
+}
+
+impl<'a> RecordDeserializer<'a> {
+  #[inline]
+  fn single(&self) -> Result<FieldDeserializer<'a>, DeError> {
+    match self.record.len() {
+      1 => Ok(FieldDeserializer(&self.record[0])),
+      len => Err(DeError::invalid_length(len, &"a record with a single field"))
+    }
+  }
+}
+
+macro_rules! forward_single {
+  ($($method:ident($($arg:ident: $type:ty),*);)*) => {
+    $(
+      #[inline]
+      fn $method<V>(self, $($arg: $type,)* visitor: V) -> Result<V::Value, Self::Error>
+      where V: Visitor<'d> {
+        self.single()?.$method($($arg,)* visitor)
+      }
+    )*
+  };
+}
+
+impl<'a, 'd> Deserializer<'d> for RecordDeserializer<'a> {
+  type Error = DeError;
+
+  fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    match self.headers {
+      Some(headers) => {
+        let fields = headers.iter().zip(self.record.iter());
+        visitor.visit_map(MapDeserializer::new(fields.map(|(key, value)| (FieldDeserializer(key), FieldDeserializer(value)))))
+      },
+      None => self.deserialize_seq(visitor)
+    }
+  }
+
+  #[inline]
+  fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_seq(SeqDeserializer::new(self.record.iter().map(FieldDeserializer)))
+  }
+
+  #[inline]
+  fn deserialize_tuple<V>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    self.deserialize_seq(visitor)
+  }
+
+  #[inline]
+  fn deserialize_tuple_struct<V>(self, _: &'static str, _: usize, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    self.deserialize_seq(visitor)
+  }
+
+  #[inline]
+  fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    self.deserialize_any(visitor)
+  }
+
+  #[inline]
+  fn deserialize_struct<V>(self, _: &'static str, _: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    self.deserialize_any(visitor)
+  }
+
+  #[inline]
+  fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_newtype_struct(self)
+  }
+
+  #[inline]
+  fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_some(self)
+  }
+
+  #[inline]
+  fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_unit()
+  }
+
+  forward_single! {
+    deserialize_bool();
+    deserialize_i8();
+    deserialize_i16();
+    deserialize_i32();
+    deserialize_i64();
+    deserialize_i128();
+    deserialize_u8();
+    deserialize_u16();
+    deserialize_u32();
+    deserialize_u64();
+    deserialize_u128();
+    deserialize_f32();
+    deserialize_f64();
+    deserialize_char();
+    deserialize_str();
+    deserialize_string();
+    deserialize_bytes();
+    deserialize_byte_buf();
+    deserialize_unit();
+    deserialize_unit_struct(name: &'static str);
+    deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+    deserialize_identifier();
+  }
+}
@@ -0,0 +1,130 @@
+//! A deserializer for formats that store every value as a string, such as CSV and INI.
+
+use serde::de::{Deserializer, Error as _, IntoDeserializer, Unexpected, Visitor};
+use serde::de::value::Error as DeError;
+
+/// Deserializes a single field, parsing it as whatever type is requested, or as a string if no type is requested.
+pub(super) struct FieldDeserializer<'a>(pub(super) &'a str);
+
+macro_rules! deserialize_parsed {
+  ($($method:ident => $visit:ident;)*) => {
+    $(
+      #[inline]
+      fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+      where V: Visitor<'d> {
+        match self.0.parse() {
+          Ok(value) => visitor.$visit(value),
+          Err(_) => Err(DeError::invalid_value(Unexpected::Str(self.0), &visitor))
+        }
+      }
+    )*
+  };
+}
+
+impl<'a, 'd> Deserializer<'d> for FieldDeserializer<'a> {
+  type Error = DeError;
+
+  /// Values of unknown type are strings, since guessing a type would change values such as `007` or `nan`.
+  #[inline]
+  fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_str(self.0)
+  }
+
+  deserialize_parsed! {
+    deserialize_bool => visit_bool;
+    deserialize_i8 => visit_i8;
+    deserialize_i16 => visit_i16;
+    deserialize_i32 => visit_i32;
+    deserialize_i64 => visit_i64;
+    deserialize_i128 => visit_i128;
+    deserialize_u8 => visit_u8;
+    deserialize_u16 => visit_u16;
+    deserialize_u32 => visit_u32;
+    deserialize_u64 => visit_u64;
+    deserialize_u128 => visit_u128;
+    deserialize_f32 => visit_f32;
+    deserialize_f64 => visit_f64;
+    deserialize_char => visit_char;
+  }
+
+  #[inline]
+  fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_str(self.0)
+  }
+
+  #[inline]
+  fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_str(self.0)
+  }
+
+  #[inline]
+  fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_bytes(self.0.as_bytes())
+  }
+
+  #[inline]
+  fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_bytes(self.0.as_bytes())
+  }
+
+  #[inline]
+  fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    if self.0.is_empty() { visitor.visit_none() } else { visitor.visit_some(self) }
+  }
+
+  #[inline]
+  fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_unit()
+  }
+
+  #[inline]
+  fn deserialize_unit_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_unit()
+  }
+
+  #[inline]
+  fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_newtype_struct(self)
+  }
+
+  #[inline]
+  fn deserialize_enum<V>(self, _: &'static str, _: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_enum(self.0.into_deserializer())
+  }
+
+  #[inline]
+  fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_str(self.0)
+  }
+
+  #[inline]
+  fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_unit()
+  }
+
+  serde::forward_to_deserialize_any! {
+    <V: Visitor<'d>>
+    seq tuple tuple_struct map struct
+  }
+}
+
+impl<'a, 'd> IntoDeserializer<'d, DeError> for FieldDeserializer<'a> {
+  type Deserializer = Self;
+
+  #[inline]
+  fn into_deserializer(self) -> Self {
+    self
+  }
+}
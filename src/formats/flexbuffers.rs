@@ -0,0 +1,39 @@
+//! FlexBuffers serialization/deserialization, via the [`flexbuffers`] crate.
+//!
+//! FlexBuffers is a schemaless binary format that can be read without parsing it first,
+//! so deserializing from a slice can borrow strings and byte buffers straight from the input.
+//! The root of a buffer is stored at its end, so reading from a stream reads it to the end.
+//!
+//! [`flexbuffers`]: https://crates.io/crates/flexbuffers
+
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
+use serde::ser::Serialize;
+use serde_flexbuffers::Reader;
+use std::io::{Read, Write};
+
+use crate::traits::{SerdeBytes, SerdeStream, Extension, Metadata};
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct FlexBuffers;
+
+function!(to_vec, super::map_err, serde_flexbuffers::to_vec);
+function!(from_slice, super::map_err, serde_flexbuffers::from_slice);
+function!(from_slice_seed, |t| t, |seed, data| Ok(seed.deserialize(Reader::get_root(data)?)?));
+function!(to_writer, |t| t, |writer, value| {
+  {writer}.write_all(&to_vec(value)?)?;
+  Ok(())
+});
+function!(from_reader, |t| t, |reader| {
+  let mut data = Vec::new();
+  {reader}.read_to_end(&mut data)?;
+  Ok(serde_flexbuffers::from_slice(&data)?)
+});
+function!(from_reader_seed, |t| t, |seed, reader| {
+  let mut data = Vec::new();
+  {reader}.read_to_end(&mut data)?;
+  Ok(seed.deserialize(Reader::get_root(data.as_slice())?)?)
+});
+
+implement!(FlexBuffers, SerdeBytes);
+implement!(FlexBuffers, SerdeStream);
+implement!(FlexBuffers, Metadata, ["flexbuffers"], ["application/x-flexbuffers"]);
@@ -0,0 +1,82 @@
+//! INI serialization/deserialization, via the [`rust-ini`] crate.
+//!
+//! Only maps and structs can be serialized. Their fields can be strings, numbers, booleans, unit,
+//! or `Option`s of those, or maps and structs of such fields, which become sections.
+//! Anything nested more deeply fails to serialize.
+//!
+//! Every value is stored as a string, and is parsed as whatever type is requested when deserializing.
+//! Values read without a type, such as into a `serde_json::Value`, are left as strings.
+//! Whitespace at the start and end of values is not preserved.
+//!
+//! Untyped maps hold general keys and sections side by side, so a file with both can only be read
+//! into a map whose values can be either a scalar or a map, such as a `serde_json::Value`.
+//! A `BTreeMap<String, String>` can only read files without sections, and a
+//! `BTreeMap<String, BTreeMap<String, String>>` only files without general keys.
+//!
+//! [`rust-ini`]: https://crates.io/crates/rust-ini
+
+use rust_ini::{LineSeparator, WriteOption};
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
+use serde::ser::Serialize;
+use std::io::{Read, Write};
+
+use super::sections::Sections;
+use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension, Metadata};
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Ini;
+
+function!(to_string, |t| t, |value| Ok(String::from_utf8(to_vec(value)?)?));
+function!(from_str, |t| t, |data| from_str_seed(std::marker::PhantomData, data));
+function!(from_str_seed, |t| t, |seed, data| deserialize(seed, rust_ini::Ini::load_from_str(data)?));
+function!(to_vec, |t| t, |value| {
+  let mut data = Vec::new();
+  to_writer(&mut data, value)?;
+  Ok(data)
+});
+function!(from_slice, |t| t, |data| from_str(std::str::from_utf8(data)?));
+function!(from_slice_seed, |t| t, |seed, data| from_str_seed(seed, std::str::from_utf8(data)?));
+function!(to_writer, |t| t, |writer, value| {
+  // `LineSeparator::CR` is actually a line feed
+  let options = WriteOption { line_separator: LineSeparator::CR, ..WriteOption::default() };
+  serialize(value)?.write_to_opt(&mut { writer }, options).map_err(super::map_err)
+});
+function!(from_reader, |t| t, |reader| from_reader_seed(std::marker::PhantomData, reader));
+function!(from_reader_seed, |t| t, |seed, reader| deserialize(seed, rust_ini::Ini::read_from(&mut { reader })?));
+
+implement!(Ini, SerdeText);
+implement!(Ini, SerdeBytes);
+implement!(Ini, SerdeStream);
+implement!(Ini, Metadata, ["ini", "cfg"], ["text/x-ini"]);
+
+fn serialize<T>(value: &T) -> Result<rust_ini::Ini, crate::Error>
+where T: Serialize + ?Sized {
+  let document = Sections::serialize("INI", value)?;
+  let mut ini = rust_ini::Ini::new();
+  for (key, value) in document.general {
+    ini.with_general_section().set(key, value);
+  }
+
+  for (name, entries) in document.sections {
+    let mut section = ini.with_section(Some(name));
+    for (key, value) in entries {
+      section.set(key, value);
+    }
+  }
+
+  Ok(ini)
+}
+
+fn deserialize<'d, S>(seed: S, ini: rust_ini::Ini) -> Result<S::Value, crate::Error>
+where S: DeserializeSeed<'d> {
+  let mut document = Sections::default();
+  for (name, properties) in &ini {
+    let entries = properties.iter().map(|(key, value)| (key.to_owned(), value.to_owned()));
+    match name {
+      Some(name) => document.sections.push((name.to_owned(), entries.collect())),
+      None => document.general.extend(entries)
+    }
+  }
+
+  Ok(seed.deserialize(document.deserializer(false))?)
+}
@@ -2,26 +2,151 @@
 //!
 //! [`serde_json`]: https://crates.io/crates/serde_json
 
-use serde::de::{Deserialize, DeserializeOwned};
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
 use serde::ser::Serialize;
+use serde_json::ser::PrettyFormatter;
+use std::borrow::Cow;
 use std::io::{Read, Write};
 
-use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension};
+use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension, Metadata};
 
-#[derive(Debug, Copy, Clone, Default)]
-pub struct Json;
+/// JSON format options.
+///
+/// The free functions in this module, and `Json::default()`, pretty-print with two spaces of indentation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Json {
+  indent: Cow<'static, str>
+}
+
+impl Json {
+  /// Creates a new `Json` with the default options.
+  #[inline]
+  pub const fn new() -> Self {
+    Json { indent: Cow::Borrowed("  ") }
+  }
+
+  /// Sets the string used to indent each level of pretty-printed output.
+  #[inline]
+  pub fn with_indent(mut self, indent: impl Into<Cow<'static, str>>) -> Self {
+    self.indent = indent.into();
+    self
+  }
+
+  /// The string used to indent each level of pretty-printed output.
+  #[inline]
+  pub fn indent(&self) -> &str {
+    &self.indent
+  }
+}
+
+impl Default for Json {
+  #[inline]
+  fn default() -> Self {
+    Json::new()
+  }
+}
 
 function!(to_string_pretty, super::map_err, serde_json::to_string_pretty);
 function!(to_string, super::map_err, serde_json::to_string);
 function!(from_str, super::map_err, serde_json::from_str);
+function!(from_str_seed, super::map_err, |seed, data| deserialize_seed(seed, serde_json::Deserializer::from_str(data)));
 function!(to_vec_pretty, super::map_err, serde_json::to_vec_pretty);
 function!(to_vec, super::map_err, serde_json::to_vec);
 function!(from_slice, super::map_err, serde_json::from_slice);
+function!(from_slice_seed, super::map_err, |seed, data| deserialize_seed(seed, serde_json::Deserializer::from_slice(data)));
 function!(to_writer_pretty, super::map_err, serde_json::to_writer_pretty);
 function!(to_writer, super::map_err, serde_json::to_writer);
 function!(from_reader, super::map_err, serde_json::from_reader);
+function!(from_reader_seed, super::map_err, |seed, reader| deserialize_seed(seed, serde_json::Deserializer::from_reader(reader)));
+
+impl SerdeText for Json {
+  #[inline]
+  fn to_string_pretty<T>(&self, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    Ok(String::from_utf8(self.to_vec_pretty(value)?)?)
+  }
+
+  #[inline]
+  fn to_string<T>(&self, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    to_string(value)
+  }
+
+  #[inline]
+  fn from_str<'d, T>(&self, data: &'d str) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_str(data)
+  }
+
+  #[inline]
+  fn from_str_seed<'d, S>(&self, seed: S, data: &'d str) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_str_seed(seed, data)
+  }
+}
+
+impl SerdeBytes for Json {
+  #[inline]
+  fn to_vec_pretty<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    let mut data = Vec::with_capacity(128);
+    self.to_writer_pretty(&mut data, value)?;
+    Ok(data)
+  }
+
+  #[inline]
+  fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    to_vec(value)
+  }
+
+  #[inline]
+  fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_slice(data)
+  }
+
+  #[inline]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_slice_seed(seed, data)
+  }
+}
+
+impl SerdeStream for Json {
+  #[inline]
+  fn to_writer_pretty<W, T>(&self, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    let formatter = PrettyFormatter::with_indent(self.indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(writer, formatter);
+    value.serialize(&mut serializer).map_err(super::map_err)
+  }
+
+  #[inline]
+  fn to_writer<W, T>(&self, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    to_writer(writer, value)
+  }
+
+  #[inline]
+  fn from_reader<R, T>(&self, reader: R) -> Result<T, crate::Error>
+  where R: Read, T: DeserializeOwned {
+    from_reader(reader)
+  }
+
+  #[inline]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    from_reader_seed(seed, reader)
+  }
+}
+
+implement!(Json, Metadata, ["json"], ["application/json", "text/json"]);
 
-implement!(Json, SerdeTextPretty);
-implement!(Json, SerdeBytesPretty);
-implement!(Json, SerdeStreamPretty);
-implement!(Json, Extension, "json");
+#[inline]
+fn deserialize_seed<'d, R, S>(seed: S, mut deserializer: serde_json::Deserializer<R>) -> serde_json::Result<S::Value>
+where R: serde_json::de::Read<'d>, S: DeserializeSeed<'d> {
+  let value = seed.deserialize(&mut deserializer)?;
+  deserializer.end()?;
+  Ok(value)
+}
@@ -0,0 +1,297 @@
+//! JSON5 serialization/deserialization, via the [`json5`] crate.
+//!
+//! JSON5 is a superset of JSON that allows comments, trailing commas, unquoted keys
+//! and a few other conveniences, which makes it well suited to hand-edited files.
+//!
+//! [`json5`]: https://crates.io/crates/json5
+
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
+use serde::ser::Serialize;
+use std::borrow::Cow;
+use std::io::{Read, Write};
+
+use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension, Metadata};
+
+/// JSON5 format options, used when serializing.
+///
+/// Any JSON5 document can be deserialized regardless of these options.
+/// The free functions in this module, and `Json5::default()`, write plain JSON
+/// and pretty-print with two spaces of indentation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Json5 {
+  style: Style,
+  indent: Cow<'static, str>
+}
+
+/// The syntax used when serializing JSON5.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Style {
+  /// Plain JSON, which any JSON parser can read. Non-finite floats are written as `null`.
+  Json,
+  /// JSON5, with unquoted object keys where possible and trailing commas in pretty-printed output.
+  /// Non-finite floats are written as `NaN`, `Infinity` or `-Infinity`.
+  Json5
+}
+
+impl Json5 {
+  /// Creates a new `Json5` with the default options.
+  #[inline]
+  pub const fn new() -> Self {
+    Json5 {
+      style: Style::Json,
+      indent: Cow::Borrowed("  ")
+    }
+  }
+
+  /// Sets the syntax used when serializing.
+  #[inline]
+  pub fn with_style(mut self, style: Style) -> Self {
+    self.style = style;
+    self
+  }
+
+  /// Sets the string used to indent each level of pretty-printed output.
+  #[inline]
+  pub fn with_indent(mut self, indent: impl Into<Cow<'static, str>>) -> Self {
+    self.indent = indent.into();
+    self
+  }
+
+  /// The syntax used when serializing.
+  #[inline]
+  pub fn style(&self) -> Style {
+    self.style
+  }
+
+  /// The string used to indent each level of pretty-printed output.
+  #[inline]
+  pub fn indent(&self) -> &str {
+    &self.indent
+  }
+
+  #[inline]
+  fn serialize<T>(&self, value: &T, pretty: bool) -> Result<String, crate::Error>
+  where T: Serialize {
+    let data = serde_json5::to_string(value).map_err(super::map_err)?;
+    Ok(self.restyle(&data, pretty))
+  }
+
+  /// Re-emits the compact output of [`json5`] in this style, since it has no options of its own.
+  ///
+  /// [`json5`]: https://crates.io/crates/json5
+  fn restyle(&self, data: &str, pretty: bool) -> String {
+    let mut output = String::with_capacity(data.len());
+    let mut containers = Vec::new();
+    let mut is_key = false;
+    let mut chars = data.chars().peekable();
+    while let Some(ch) = chars.next() {
+      match ch {
+        '{' | '[' => {
+          output.push(ch);
+          if let Some(close) = chars.next_if_eq(&if ch == '{' { '}' } else { ']' }) {
+            output.push(close);
+          } else {
+            containers.push(ch);
+            is_key = ch == '{';
+            self.newline(&mut output, containers.len(), pretty);
+          }
+        },
+        '}' | ']' => {
+          containers.pop();
+          if pretty && self.style == Style::Json5 {
+            output.push(',');
+          }
+
+          self.newline(&mut output, containers.len(), pretty);
+          output.push(ch);
+        },
+        ',' => {
+          output.push(',');
+          is_key = containers.last() == Some(&'{');
+          self.newline(&mut output, containers.len(), pretty);
+        },
+        ':' => {
+          output.push_str(if pretty { ": " } else { ":" });
+        },
+        '"' => {
+          let mut string = String::new();
+          while let Some(ch) = chars.next() {
+            match ch {
+              '"' => break,
+              '\\' => {
+                string.push(ch);
+                string.extend(chars.next());
+              },
+              // `json5` leaves most control characters unescaped, which neither JSON nor JSON5 allow
+              ch if ch < ' ' => string.push_str(&format!("\\u{:04x}", ch as u32)),
+              ch => string.push(ch)
+            }
+          }
+
+          if is_key && self.style == Style::Json5 && is_identifier(&string) {
+            output.push_str(&string);
+          } else {
+            output.push('"');
+            output.push_str(&string);
+            output.push('"');
+          }
+
+          is_key = false;
+        },
+        ch => {
+          let mut token = ch.to_string();
+          while let Some(ch) = chars.next_if(|ch| !",:]}".contains(*ch)) {
+            token.push(ch);
+          }
+
+          let token = match token.as_str() {
+            "NaN" | "Infinity" | "-Infinity" if self.style == Style::Json => "null",
+            token => token
+          };
+
+          // `json5` writes non-string map keys, such as integers, without quotes
+          if is_key {
+            output.push('"');
+            output.push_str(token);
+            output.push('"');
+          } else {
+            output.push_str(token);
+          }
+
+          is_key = false;
+        }
+      }
+    }
+
+    output
+  }
+
+  #[inline]
+  fn newline(&self, output: &mut String, depth: usize, pretty: bool) {
+    if pretty {
+      output.push('\n');
+      for _ in 0..depth {
+        output.push_str(&self.indent);
+      }
+    }
+  }
+}
+
+impl Default for Json5 {
+  #[inline]
+  fn default() -> Self {
+    Json5::new()
+  }
+}
+
+function!(to_string_pretty, |t| t, |value| Json5::new().to_string_pretty(value));
+function!(to_string, |t| t, |value| Json5::new().to_string(value));
+function!(from_str, super::map_err, serde_json5::from_str);
+function!(from_str_seed, super::map_err, |seed, data| seed.deserialize(&mut serde_json5::Deserializer::from_str(data)?));
+function!(to_vec_pretty, |t| t, |value| Json5::new().to_vec_pretty(value));
+function!(to_vec, |t| t, |value| Json5::new().to_vec(value));
+function!(from_slice, |t| t, |data| Ok(serde_json5::from_str(std::str::from_utf8(data)?)?));
+function!(from_slice_seed, |t| t, |seed, data| {
+  Ok(seed.deserialize(&mut serde_json5::Deserializer::from_str(std::str::from_utf8(data)?)?)?)
+});
+function!(to_writer_pretty, |t| t, |writer, value| Json5::new().to_writer_pretty(writer, value));
+function!(to_writer, |t| t, |writer, value| Json5::new().to_writer(writer, value));
+function!(from_reader, |t| t, |reader| {
+  let mut data = String::new();
+  {reader}.read_to_string(&mut data)?;
+  Ok(serde_json5::from_str(&data)?)
+});
+function!(from_reader_seed, |t| t, |seed, reader| {
+  let mut data = String::new();
+  {reader}.read_to_string(&mut data)?;
+  Ok(seed.deserialize(&mut serde_json5::Deserializer::from_str(&data)?)?)
+});
+
+impl SerdeText for Json5 {
+  #[inline]
+  fn to_string_pretty<T>(&self, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    self.serialize(value, true)
+  }
+
+  #[inline]
+  fn to_string<T>(&self, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    self.serialize(value, false)
+  }
+
+  #[inline]
+  fn from_str<'d, T>(&self, data: &'d str) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_str(data)
+  }
+
+  #[inline]
+  fn from_str_seed<'d, S>(&self, seed: S, data: &'d str) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_str_seed(seed, data)
+  }
+}
+
+impl SerdeBytes for Json5 {
+  #[inline]
+  fn to_vec_pretty<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    self.serialize(value, true).map(String::into_bytes)
+  }
+
+  #[inline]
+  fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    self.serialize(value, false).map(String::into_bytes)
+  }
+
+  #[inline]
+  fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_slice(data)
+  }
+
+  #[inline]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_slice_seed(seed, data)
+  }
+}
+
+impl SerdeStream for Json5 {
+  #[inline]
+  fn to_writer_pretty<W, T>(&self, mut writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    writer.write_all(self.serialize(value, true)?.as_bytes())?;
+    Ok(())
+  }
+
+  #[inline]
+  fn to_writer<W, T>(&self, mut writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    writer.write_all(self.serialize(value, false)?.as_bytes())?;
+    Ok(())
+  }
+
+  #[inline]
+  fn from_reader<R, T>(&self, reader: R) -> Result<T, crate::Error>
+  where R: Read, T: DeserializeOwned {
+    from_reader(reader)
+  }
+
+  #[inline]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    from_reader_seed(seed, reader)
+  }
+}
+
+implement!(Json5, Metadata, ["json5"], ["application/json5"]);
+
+/// Checks whether an object key can be written without quotes.
+fn is_identifier(key: &str) -> bool {
+  let mut chars = key.chars();
+  chars.next().is_some_and(|ch| ch.is_alphabetic() || ch == '_' || ch == '$')
+    && chars.all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '$')
+}
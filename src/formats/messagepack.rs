@@ -3,19 +3,222 @@
 //! [`rmp`]: https://crates.io/crates/rmp
 //! [`rmp-serde`]: https://crates.io/crates/rmp-serde
 
-use serde::de::{Deserialize, DeserializeOwned};
+use rmp_core::Marker;
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
 use serde::ser::Serialize;
 use std::io::{Read, Write};
 
-use crate::traits::{SerdeBytes, SerdeStream};
+use crate::traits::{SerdeBytes, SerdeStream, Extension, Metadata};
 
-#[derive(Debug, Copy, Clone, Default)]
-pub struct MessagePack;
+/// MessagePack format options, used when serializing.
+/// Data written with any combination of options can be read back regardless of the options used to read it.
+///
+/// The free functions in this module, and `MessagePack::default()`, use the most compact representation:
+/// structs as tuples, enum variants as integers and byte buffers as `bin` values.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MessagePack {
+  structs: StructEncoding,
+  variants: VariantEncoding,
+  bytes: BytesEncoding
+}
+
+/// How structs are encoded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum StructEncoding {
+  /// Structs are encoded as arrays of their fields, without field names.
+  Tuple,
+  /// Structs are encoded as maps from field names to fields.
+  Map
+}
+
+/// How enum variants are identified.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum VariantEncoding {
+  /// Variants are identified by their index.
+  Integer,
+  /// Variants are identified by their name.
+  String
+}
+
+/// How byte buffers are encoded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BytesEncoding {
+  /// Byte buffers are encoded as `bin` values.
+  Binary,
+  /// Byte buffers are encoded as arrays of integers, for peers that do not understand `bin` values.
+  Array
+}
+
+impl MessagePack {
+  /// Creates a new `MessagePack` with the default options.
+  #[inline]
+  pub const fn new() -> Self {
+    MessagePack {
+      structs: StructEncoding::Tuple,
+      variants: VariantEncoding::Integer,
+      bytes: BytesEncoding::Binary
+    }
+  }
+
+  /// Sets how structs are encoded.
+  #[inline]
+  pub fn with_struct_encoding(mut self, structs: StructEncoding) -> Self {
+    self.structs = structs;
+    self
+  }
+
+  /// Sets how enum variants are identified.
+  #[inline]
+  pub fn with_variant_encoding(mut self, variants: VariantEncoding) -> Self {
+    self.variants = variants;
+    self
+  }
+
+  /// Sets how byte buffers are encoded.
+  #[inline]
+  pub fn with_bytes_encoding(mut self, bytes: BytesEncoding) -> Self {
+    self.bytes = bytes;
+    self
+  }
+
+  /// How structs are encoded.
+  #[inline]
+  pub fn struct_encoding(&self) -> StructEncoding {
+    self.structs
+  }
+
+  /// How enum variants are identified.
+  #[inline]
+  pub fn variant_encoding(&self) -> VariantEncoding {
+    self.variants
+  }
+
+  /// How byte buffers are encoded.
+  #[inline]
+  pub fn bytes_encoding(&self) -> BytesEncoding {
+    self.bytes
+  }
+
+  fn serialize<W, T>(&self, writer: W, value: &T) -> Result<(), rmp_serde::encode::Error>
+  where W: Write, T: Serialize {
+    let mut serializer = rmp_serde::Serializer::new(writer);
+    match (self.structs, self.variants) {
+      (StructEncoding::Tuple, VariantEncoding::Integer) => value.serialize(&mut serializer),
+      (StructEncoding::Tuple, VariantEncoding::String) => value.serialize(&mut serializer.with_string_variants()),
+      (StructEncoding::Map, VariantEncoding::Integer) => value.serialize(&mut serializer.with_struct_map()),
+      (StructEncoding::Map, VariantEncoding::String) => value.serialize(&mut serializer.with_struct_map().with_string_variants())
+    }
+  }
+}
+
+impl Default for MessagePack {
+  #[inline]
+  fn default() -> Self {
+    MessagePack::new()
+  }
+}
 
 function!(to_vec, super::map_err, |value| rmp_serde::to_vec(value));
 function!(from_slice, super::map_err, |data| rmp_serde::from_slice(data));
+function!(from_slice_seed, super::map_err, |seed, data| seed.deserialize(&mut rmp_serde::Deserializer::from_read_ref(data)));
 function!(to_writer, super::map_err, |writer, value| rmp_serde::encode::write(&mut { writer }, value));
 function!(from_reader, super::map_err, |reader| rmp_serde::decode::from_read(reader));
+function!(from_reader_seed, super::map_err, |seed, reader| seed.deserialize(&mut rmp_serde::Deserializer::new(reader)));
+
+impl SerdeBytes for MessagePack {
+  fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    let mut data = Vec::with_capacity(128);
+    self.serialize(&mut data, value)?;
+    match self.bytes {
+      BytesEncoding::Binary => Ok(data),
+      BytesEncoding::Array => bin_to_array(&data)
+    }
+  }
+
+  #[inline]
+  fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_slice(data)
+  }
+
+  #[inline]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_slice_seed(seed, data)
+  }
+}
+
+impl SerdeStream for MessagePack {
+  fn to_writer<W, T>(&self, mut writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    match self.bytes {
+      BytesEncoding::Binary => Ok(self.serialize(writer, value)?),
+      BytesEncoding::Array => Ok(writer.write_all(&self.to_vec(value)?)?)
+    }
+  }
+
+  #[inline]
+  fn from_reader<R, T>(&self, reader: R) -> Result<T, crate::Error>
+  where R: Read, T: DeserializeOwned {
+    from_reader(reader)
+  }
+
+  #[inline]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    from_reader_seed(seed, reader)
+  }
+}
+
+implement!(MessagePack, Metadata, ["msgpack", "mpk"], ["application/msgpack", "application/x-msgpack", "application/vnd.msgpack"]);
+
+/// Rewrites every `bin` value in some MessagePack data as an array of integers,
+/// since `rmp-serde` has no option to do this itself.
+///
+/// The data is assumed to be well-formed, as it has just been written by `rmp-serde`.
+fn bin_to_array(mut data: &[u8]) -> Result<Vec<u8>, crate::Error> {
+  let mut out = Vec::with_capacity(data.len());
+  while let Some(&byte) = data.first() {
+    let marker = Marker::from_u8(byte);
+    // The size of the marker and any length that follows it, and the size of the payload after that
+    let (header, payload) = match marker {
+      Marker::Bin8 | Marker::Str8 => (2, read_len(&data[1..2])),
+      Marker::Bin16 | Marker::Str16 => (3, read_len(&data[1..3])),
+      Marker::Bin32 | Marker::Str32 => (5, read_len(&data[1..5])),
+      Marker::Ext8 => (2, read_len(&data[1..2]) + 1),
+      Marker::Ext16 => (3, read_len(&data[1..3]) + 1),
+      Marker::Ext32 => (5, read_len(&data[1..5]) + 1),
+      Marker::FixStr(len) => (1, len as usize),
+      Marker::U8 | Marker::I8 => (1, 1),
+      Marker::U16 | Marker::I16 | Marker::Array16 | Marker::Map16 | Marker::FixExt1 => (1, 2),
+      Marker::FixExt2 => (1, 3),
+      Marker::U32 | Marker::I32 | Marker::F32 | Marker::Array32 | Marker::Map32 => (1, 4),
+      Marker::FixExt4 => (1, 5),
+      Marker::U64 | Marker::I64 | Marker::F64 => (1, 8),
+      Marker::FixExt8 => (1, 9),
+      Marker::FixExt16 => (1, 17),
+      _ => (1, 0)
+    };
+
+    let (value, rest) = data.split_at(header + payload);
+    match marker {
+      Marker::Bin8 | Marker::Bin16 | Marker::Bin32 => {
+        rmp_core::encode::write_array_len(&mut out, payload as u32)?;
+        for &byte in &value[header..] {
+          rmp_core::encode::write_uint(&mut out, byte as u64)?;
+        }
+      },
+      _ => out.extend_from_slice(value)
+    }
+
+    data = rest;
+  }
+
+  Ok(out)
+}
 
-implement!(MessagePack, SerdeBytes);
-implement!(MessagePack, SerdeStream);
+#[inline]
+fn read_len(bytes: &[u8]) -> usize {
+  bytes.iter().fold(0, |len, &byte| len << 8 | byte as usize)
+}
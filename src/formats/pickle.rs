@@ -0,0 +1,126 @@
+//! Python pickle serialization/deserialization, via the [`serde-pickle`] crate.
+//!
+//! Only pickles of plain Python values (numbers, strings, bytes, lists, tuples, sets and dicts)
+//! can be deserialized; pickles of class instances are rejected.
+//!
+//! [`serde-pickle`]: https://crates.io/crates/serde-pickle
+
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
+use serde::ser::Serialize;
+use serde_pickle::{DeOptions, SerOptions};
+use std::io::{Read, Write};
+
+use crate::traits::{SerdeBytes, SerdeStream, Extension, Metadata};
+
+/// Pickle format options, used when serializing.
+///
+/// Pickles of any protocol can be deserialized regardless of these options.
+/// The free functions in this module, and `Pickle::default()`, write protocol 3.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Pickle {
+  protocol: Protocol
+}
+
+/// The pickle protocol version written when serializing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Protocol {
+  /// Protocol 2, which can also be read by Python 2.3 and later.
+  V2,
+  /// Protocol 3, which can be read by Python 3.0 and later, and distinguishes `bytes` from `str`.
+  V3
+}
+
+impl Pickle {
+  /// Creates a new `Pickle` with the default options.
+  #[inline]
+  pub const fn new() -> Self {
+    Pickle {
+      protocol: Protocol::V3
+    }
+  }
+
+  /// Sets the protocol version written when serializing.
+  #[inline]
+  pub fn with_protocol(mut self, protocol: Protocol) -> Self {
+    self.protocol = protocol;
+    self
+  }
+
+  /// The protocol version written when serializing.
+  #[inline]
+  pub fn protocol(&self) -> Protocol {
+    self.protocol
+  }
+
+  #[inline]
+  fn options(&self) -> SerOptions {
+    match self.protocol {
+      Protocol::V2 => SerOptions::new().proto_v2(),
+      Protocol::V3 => SerOptions::new()
+    }
+  }
+}
+
+impl Default for Pickle {
+  #[inline]
+  fn default() -> Self {
+    Pickle::new()
+  }
+}
+
+function!(to_vec, |t| t, |value| Pickle::new().to_vec(value));
+function!(from_slice, super::map_err, |data| serde_pickle::from_slice(data, DeOptions::new()));
+function!(from_slice_seed, |t| t, |seed, data| deserialize_seed(seed, data));
+function!(to_writer, |t| t, |writer, value| Pickle::new().to_writer(writer, value));
+function!(from_reader, super::map_err, |reader| serde_pickle::from_reader(reader, DeOptions::new()));
+function!(from_reader_seed, |t| t, |seed, reader| deserialize_seed(seed, reader));
+
+impl SerdeBytes for Pickle {
+  #[inline]
+  fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    serde_pickle::to_vec(value, self.options()).map_err(super::map_err)
+  }
+
+  #[inline]
+  fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_slice(data)
+  }
+
+  #[inline]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_slice_seed(seed, data)
+  }
+}
+
+impl SerdeStream for Pickle {
+  #[inline]
+  fn to_writer<W, T>(&self, mut writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    serde_pickle::to_writer(&mut writer, value, self.options()).map_err(super::map_err)
+  }
+
+  #[inline]
+  fn from_reader<R, T>(&self, reader: R) -> Result<T, crate::Error>
+  where R: Read, T: DeserializeOwned {
+    from_reader(reader)
+  }
+
+  #[inline]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    from_reader_seed(seed, reader)
+  }
+}
+
+implement!(Pickle, Metadata, ["pickle", "pkl"], ["application/x-python-pickle"]);
+
+fn deserialize_seed<'d, S, R>(seed: S, reader: R) -> Result<S::Value, crate::Error>
+where S: DeserializeSeed<'d>, R: Read {
+  let mut deserializer = serde_pickle::Deserializer::new(reader, DeOptions::new());
+  let value = seed.deserialize(&mut deserializer)?;
+  deserializer.end()?;
+  Ok(value)
+}
@@ -0,0 +1,358 @@
+//! Apple property list serialization/deserialization, via the [`plist`] crate.
+//!
+//! Property lists can be written as either XML or binary, depending on the [`Encoding`] option.
+//! Either kind, as well as the older ASCII kind, is detected automatically when deserializing.
+//! Property lists have no null value, so `None` struct fields and map values are left out,
+//! and other `Option`s are written as dictionaries with a single `None` or `Some` key.
+//!
+//! [`plist`]: https://crates.io/crates/plist
+//! [`Encoding`]: ./enum.Encoding.html
+
+use serde::de::{self, Deserialize, DeserializeOwned, DeserializeSeed, Deserializer, Error as _, IntoDeserializer, Unexpected, Visitor};
+use serde::de::value::{MapAccessDeserializer, MapDeserializer, SeqDeserializer};
+use serde::ser::Serialize;
+use serde_plist::{Dictionary, Value};
+use std::io::{Cursor, Read, Write};
+
+use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension, Metadata};
+
+/// Property list format options, used when serializing.
+///
+/// Property lists of either encoding can be deserialized regardless of these options,
+/// and serializing to a `String` always writes XML.
+/// The free functions in this module, and `Plist::default()`, write XML.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Plist {
+  encoding: Encoding
+}
+
+/// The kind of property list written when serializing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Encoding {
+  /// XML, readable and editable by hand.
+  Xml,
+  /// The `bplist00` binary format, which is smaller and faster to read.
+  Binary
+}
+
+impl Plist {
+  /// Creates a new `Plist` with the default options.
+  #[inline]
+  pub const fn new() -> Self {
+    Plist {
+      encoding: Encoding::Xml
+    }
+  }
+
+  /// Sets the kind of property list written when serializing to bytes or a writer.
+  #[inline]
+  pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+    self.encoding = encoding;
+    self
+  }
+
+  /// The kind of property list written when serializing to bytes or a writer.
+  #[inline]
+  pub fn encoding(&self) -> Encoding {
+    self.encoding
+  }
+
+  /// Whether serializing to bytes or a writer produces text, which is the case for XML.
+  #[inline]
+  pub fn is_text(&self) -> bool {
+    self.encoding == Encoding::Xml
+  }
+}
+
+impl Default for Plist {
+  #[inline]
+  fn default() -> Self {
+    Plist::new()
+  }
+}
+
+function!(to_string, |t| t, |value| Ok(String::from_utf8(to_vec(value)?)?));
+function!(from_str, |t| t, |data| from_slice(data.as_bytes()));
+function!(from_str_seed, |t| t, |seed, data| from_slice_seed(seed, data.as_bytes()));
+function!(to_vec, |t| t, |value| Plist::new().to_vec(value));
+function!(from_slice, |t| t, |data| from_slice_seed(std::marker::PhantomData, data));
+function!(from_slice_seed, |t| t, |seed, data| {
+  // the root is only `None` when nothing was written at all
+  if data.iter().all(u8::is_ascii_whitespace) {
+    return Ok(seed.deserialize(IntoDeserializer::<serde_plist::Error>::into_deserializer(()))?);
+  }
+
+  let value = Value::from_reader(Cursor::new(data))?;
+  Ok(seed.deserialize(ValueDeserializer::new(&value, OptionMode::Root))?)
+});
+function!(to_writer, |t| t, |writer, value| Plist::new().to_writer(writer, value));
+function!(from_reader, |t| t, |reader| from_reader_seed(std::marker::PhantomData, reader));
+function!(from_reader_seed, |t| t, |seed, reader| {
+  // detecting the encoding needs to seek back to the start
+  let mut data = Vec::new();
+  {reader}.read_to_end(&mut data)?;
+  from_slice_seed(seed, &data)
+});
+
+impl SerdeText for Plist {
+  #[inline]
+  fn to_string<T>(&self, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    to_string(value)
+  }
+
+  #[inline]
+  fn from_str<'d, T>(&self, data: &'d str) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_str(data)
+  }
+
+  #[inline]
+  fn from_str_seed<'d, S>(&self, seed: S, data: &'d str) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_str_seed(seed, data)
+  }
+}
+
+impl SerdeBytes for Plist {
+  #[inline]
+  fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    let mut data = Vec::new();
+    self.to_writer(&mut data, value)?;
+    Ok(data)
+  }
+
+  #[inline]
+  fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_slice(data)
+  }
+
+  #[inline]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_slice_seed(seed, data)
+  }
+}
+
+impl SerdeStream for Plist {
+  #[inline]
+  fn to_writer<W, T>(&self, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    match self.encoding {
+      Encoding::Xml => serde_plist::to_writer_xml(writer, value).map_err(super::map_err),
+      Encoding::Binary => serde_plist::to_writer_binary(writer, value).map_err(super::map_err)
+    }
+  }
+
+  #[inline]
+  fn from_reader<R, T>(&self, reader: R) -> Result<T, crate::Error>
+  where R: Read, T: DeserializeOwned {
+    from_reader(reader)
+  }
+
+  #[inline]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    from_reader_seed(seed, reader)
+  }
+}
+
+implement!(Plist, Metadata, ["plist"], ["application/x-plist"]);
+
+/// Where a value is, which decides how an `Option` is read, mirroring [`plist`]'s own deserializer.
+///
+/// [`plist`]: https://crates.io/crates/plist
+#[derive(Copy, Clone)]
+enum OptionMode {
+  /// The whole document, which is `Some` since it is not empty.
+  Root,
+  /// A struct field, which is left out when it is `None`.
+  StructField,
+  /// Anything else, which is a dictionary with a single `None` or `Some` key.
+  Explicit
+}
+
+/// Deserializes a parsed value, reading the same representation that [`plist`] serializes.
+///
+/// [`plist`]: https://crates.io/crates/plist
+struct ValueDeserializer<'a> {
+  value: &'a Value,
+  option: OptionMode,
+  /// Whether a `plist::Value` is being deserialized, which reads dates and UIDs as their own types.
+  in_plist_value: bool
+}
+
+impl<'a> ValueDeserializer<'a> {
+  #[inline]
+  fn new(value: &'a Value, option: OptionMode) -> Self {
+    ValueDeserializer { value, option, in_plist_value: false }
+  }
+
+  #[inline]
+  fn child(&self, value: &'a Value, option: OptionMode) -> Self {
+    ValueDeserializer { value, option, in_plist_value: self.in_plist_value }
+  }
+
+  #[inline]
+  fn invalid_type(&self, expected: &str) -> serde_plist::Error {
+    let unexpected = match self.value {
+      Value::Array(_) => Unexpected::Seq,
+      Value::Dictionary(_) => Unexpected::Map,
+      Value::Boolean(value) => Unexpected::Bool(*value),
+      Value::Data(value) => Unexpected::Bytes(value),
+      Value::Date(_) => Unexpected::Other("date"),
+      Value::Real(value) => Unexpected::Float(*value),
+      Value::Integer(_) => Unexpected::Other("integer"),
+      Value::String(value) => Unexpected::Str(value),
+      Value::Uid(_) => Unexpected::Other("UID"),
+      _ => Unexpected::Other("unknown value")
+    };
+
+    serde_plist::Error::invalid_type(unexpected, &expected)
+  }
+
+  fn visit_dictionary<'d, V>(&self, dictionary: &'a Dictionary, option: OptionMode, visitor: V) -> Result<V::Value, serde_plist::Error>
+  where V: Visitor<'d> {
+    let entries = dictionary.iter().map(|(key, value)| (key.as_str(), self.child(value, option)));
+    visitor.visit_map(MapDeserializer::new(entries))
+  }
+}
+
+impl<'a, 'd> Deserializer<'d> for ValueDeserializer<'a> {
+  type Error = serde_plist::Error;
+
+  fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    match self.value {
+      Value::Array(elements) => {
+        visitor.visit_seq(SeqDeserializer::new(elements.iter().map(|element| self.child(element, OptionMode::Explicit))))
+      },
+      Value::Dictionary(dictionary) => self.visit_dictionary(dictionary, OptionMode::Explicit, visitor),
+      Value::Boolean(value) => visitor.visit_bool(*value),
+      Value::Data(value) => visitor.visit_bytes(value),
+      Value::Date(date) if self.in_plist_value => {
+        let entries = std::iter::once(("PLIST-DATE", date.to_xml_format()));
+        visitor.visit_enum(MapAccessDeserializer::new(MapDeserializer::new(entries)))
+      },
+      Value::Date(date) => visitor.visit_string(date.to_xml_format()),
+      Value::Real(value) => visitor.visit_f64(*value),
+      Value::Integer(integer) => match (integer.as_unsigned(), integer.as_signed()) {
+        (Some(value), _) => visitor.visit_u64(value),
+        (None, Some(value)) => visitor.visit_i64(value),
+        (None, None) => Err(self.invalid_type("an integer that fits in 64 bits"))
+      },
+      Value::String(value) => visitor.visit_str(value),
+      Value::Uid(uid) if self.in_plist_value => {
+        let entries = std::iter::once(("PLIST-UID", uid.get()));
+        visitor.visit_enum(MapAccessDeserializer::new(MapDeserializer::new(entries)))
+      },
+      Value::Uid(uid) => visitor.visit_u64(uid.get()),
+      _ => Err(self.invalid_type("a property list value"))
+    }
+  }
+
+  fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    match self.option {
+      OptionMode::Root | OptionMode::StructField => visitor.visit_some(ValueDeserializer { option: OptionMode::Explicit, ..self }),
+      OptionMode::Explicit => match self.value.as_dictionary().map(|dictionary| dictionary.iter().next()) {
+        Some(Some((key, _))) if key == "None" => visitor.visit_none(),
+        Some(Some((key, value))) if key == "Some" => visitor.visit_some(self.child(value, OptionMode::Explicit)),
+        _ => Err(self.invalid_type("a dictionary with a single `None` or `Some` key"))
+      }
+    }
+  }
+
+  #[inline]
+  fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    match self.value {
+      Value::String(_) => visitor.visit_unit(),
+      _ => Err(self.invalid_type("an empty string"))
+    }
+  }
+
+  #[inline]
+  fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    let in_plist_value = self.in_plist_value || name == "PLIST-VALUE";
+    visitor.visit_newtype_struct(ValueDeserializer { in_plist_value, ..self })
+  }
+
+  #[inline]
+  fn deserialize_struct<V>(self, _: &'static str, _: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    match self.value {
+      Value::Dictionary(dictionary) => self.visit_dictionary(dictionary, OptionMode::StructField, visitor),
+      _ => Err(self.invalid_type("a dictionary"))
+    }
+  }
+
+  fn deserialize_enum<V>(self, name: &'static str, variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    match self.value {
+      Value::String(variant) => variant.as_str().into_deserializer().deserialize_enum(name, variants, visitor),
+      Value::Dictionary(dictionary) if dictionary.len() == 1 => visitor.visit_enum(self),
+      _ => Err(self.invalid_type("a string or a dictionary with a single key"))
+    }
+  }
+
+  serde::forward_to_deserialize_any! {
+    <V: Visitor<'d>>
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+    bytes byte_buf unit_struct seq tuple tuple_struct map identifier ignored_any
+  }
+}
+
+impl<'a, 'd> IntoDeserializer<'d, serde_plist::Error> for ValueDeserializer<'a> {
+  type Deserializer = Self;
+
+  #[inline]
+  fn into_deserializer(self) -> Self {
+    self
+  }
+}
+
+/// Reads an enum variant with data, which is a dictionary with the name of the variant as its only key.
+impl<'a, 'd> de::EnumAccess<'d> for ValueDeserializer<'a> {
+  type Error = serde_plist::Error;
+  type Variant = Self;
+
+  #[inline]
+  fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self), Self::Error>
+  where S: DeserializeSeed<'d> {
+    match self.value.as_dictionary().and_then(|dictionary| dictionary.iter().next()) {
+      Some((variant, value)) => Ok((seed.deserialize(IntoDeserializer::<Self::Error>::into_deserializer(variant.as_str()))?, self.child(value, OptionMode::Explicit))),
+      None => Err(self.invalid_type("a dictionary with a single key"))
+    }
+  }
+}
+
+impl<'a, 'd> de::VariantAccess<'d> for ValueDeserializer<'a> {
+  type Error = serde_plist::Error;
+
+  #[inline]
+  fn unit_variant(self) -> Result<(), Self::Error> {
+    self.deserialize_unit(de::IgnoredAny).map(drop)
+  }
+
+  #[inline]
+  fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, Self::Error>
+  where S: DeserializeSeed<'d> {
+    seed.deserialize(self)
+  }
+
+  #[inline]
+  fn tuple_variant<V>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    self.deserialize_seq(visitor)
+  }
+
+  #[inline]
+  fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    self.deserialize_struct("", fields, visitor)
+  }
+}
@@ -0,0 +1,148 @@
+//! Postcard serialization/deserialization, via the [`postcard`] crate.
+//!
+//! [`postcard`]: https://crates.io/crates/postcard
+
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
+use serde::ser::Serialize;
+use std::io::{self, Read, Write};
+use std::marker::PhantomData;
+
+use crate::traits::{SerdeBytes, SerdeStream, Extension, Metadata};
+
+/// Postcard format options.
+///
+/// The free functions in this module, and `Postcard::default()`, read and write unframed messages.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Postcard {
+  cobs: bool
+}
+
+impl Postcard {
+  /// Creates a new `Postcard` with the default options.
+  #[inline]
+  pub const fn new() -> Self {
+    Postcard { cobs: false }
+  }
+
+  /// Sets whether messages are framed with [COBS], as is common on serial links.
+  ///
+  /// Framed messages contain no zero bytes except for a single terminating one,
+  /// so a reader can always find where the next message starts. When reading from a stream,
+  /// only bytes up to and including the terminator are consumed, so consecutive messages
+  /// can be read from the same stream.
+  ///
+  /// Framed messages are decoded into a temporary buffer, which values cannot borrow from,
+  /// so they can only be deserialized from a reader, such as `&data[..]`;
+  /// deserializing one from a `&[u8]` returns an error.
+  ///
+  /// [COBS]: https://en.wikipedia.org/wiki/Consistent_Overhead_Byte_Stuffing
+  #[inline]
+  pub fn with_cobs(mut self, cobs: bool) -> Self {
+    self.cobs = cobs;
+    self
+  }
+
+  /// Whether messages are framed with COBS.
+  #[inline]
+  pub fn cobs(&self) -> bool {
+    self.cobs
+  }
+}
+
+impl Default for Postcard {
+  #[inline]
+  fn default() -> Self {
+    Postcard::new()
+  }
+}
+
+function!(to_vec, super::map_err, serde_postcard::to_stdvec);
+function!(from_slice, super::map_err, serde_postcard::from_bytes);
+function!(from_slice_seed, super::map_err, |seed, data| seed.deserialize(&mut serde_postcard::Deserializer::from_bytes(data)));
+function!(to_writer, super::map_err, |writer, value| serde_postcard::to_io(value, writer).map(drop));
+function!(from_reader, |t| t, |reader| {
+  let mut data = Vec::new();
+  {reader}.read_to_end(&mut data)?;
+  Ok(serde_postcard::from_bytes(&data)?)
+});
+function!(from_reader_seed, |t| t, |seed, reader| {
+  let mut data = Vec::new();
+  {reader}.read_to_end(&mut data)?;
+  Ok(seed.deserialize(&mut serde_postcard::Deserializer::from_bytes(&data))?)
+});
+
+impl SerdeBytes for Postcard {
+  #[inline]
+  fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    if self.cobs {
+      serde_postcard::to_stdvec_cobs(value).map_err(super::map_err)
+    } else {
+      to_vec(value)
+    }
+  }
+
+  #[inline]
+  fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    self.from_slice_seed(PhantomData, data)
+  }
+
+  #[inline]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    if self.cobs {
+      Err("COBS-framed Postcard messages can only be deserialized from a reader".into())
+    } else {
+      from_slice_seed(seed, data)
+    }
+  }
+}
+
+impl SerdeStream for Postcard {
+  #[inline]
+  fn to_writer<W, T>(&self, mut writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    if self.cobs {
+      writer.write_all(&self.to_vec(value)?)?;
+      Ok(())
+    } else {
+      to_writer(writer, value)
+    }
+  }
+
+  #[inline]
+  fn from_reader<R, T>(&self, reader: R) -> Result<T, crate::Error>
+  where R: Read, T: DeserializeOwned {
+    self.from_reader_seed(PhantomData, reader)
+  }
+
+  #[inline]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    if self.cobs {
+      let data = cobs::decode_vec(&read_frame(reader)?)?;
+      Ok(seed.deserialize(&mut serde_postcard::Deserializer::from_bytes(&data))?)
+    } else {
+      from_reader_seed(seed, reader)
+    }
+  }
+}
+
+implement!(Postcard, Metadata, ["postcard"], ["application/x-postcard"]);
+
+/// Reads a COBS frame up to and including its terminating zero byte.
+/// Bytes are read one at a time, so that nothing past the end of the frame is consumed.
+fn read_frame<R: Read>(reader: R) -> io::Result<Vec<u8>> {
+  let mut data = Vec::new();
+  #[allow(clippy::unbuffered_bytes)]
+  for byte in reader.bytes() {
+    let byte = byte?;
+    data.push(byte);
+    if byte == 0 {
+      return Ok(data);
+    }
+  }
+
+  Err(io::Error::new(io::ErrorKind::UnexpectedEof, "COBS frame has no terminating zero byte"))
+}
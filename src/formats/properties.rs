@@ -0,0 +1,98 @@
+//! Java `.properties` serialization/deserialization, via the [`java-properties`] crate.
+//!
+//! Only maps and structs can be serialized. Their fields can be strings, numbers, booleans, unit,
+//! or `Option`s of those, or maps and structs of such fields, whose keys are prefixed with the
+//! name of the field and a dot, such as `server.port`. Anything nested more deeply fails to serialize.
+//! When deserializing a struct, keys like `server.port` are grouped back into a `server` field if the struct has one.
+//!
+//! Every value is stored as a string, and is parsed as whatever type is requested when deserializing.
+//! Untyped values, such as the values of a `#[serde(flatten)]` map, are always strings.
+//! Files are read and written as UTF-8, rather than the ISO 8859-1 encoding used by older versions of Java.
+//!
+//! [`java-properties`]: https://crates.io/crates/java-properties
+
+use encoding_rs::UTF_8;
+use java_properties::PropertiesIter;
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
+use serde::ser::Serialize;
+use std::io::{Read, Write};
+
+use super::sections::Sections;
+use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension, Metadata};
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Properties;
+
+function!(to_string, |t| t, |value| {
+  let document = Sections::serialize("Java properties", value)?;
+  let mut output = String::new();
+  for (key, value) in &document.general {
+    write_entry(&mut output, key, value);
+  }
+
+  for (name, entries) in &document.sections {
+    for (key, value) in entries {
+      write_entry(&mut output, &format!("{}.{}", name, key), value);
+    }
+  }
+
+  Ok(output)
+});
+function!(from_str, |t| t, |data| deserialize(std::marker::PhantomData, data.as_bytes()));
+function!(from_str_seed, |t| t, |seed, data| deserialize(seed, data.as_bytes()));
+function!(to_vec, |t| t, |value| to_string(value).map(String::into_bytes));
+function!(from_slice, |t| t, |data| deserialize(std::marker::PhantomData, data));
+function!(from_slice_seed, |t| t, |seed, data| deserialize(seed, data));
+function!(to_writer, |t| t, |writer, value| {
+  {writer}.write_all(to_string(value)?.as_bytes()).map_err(super::map_err)
+});
+function!(from_reader, |t| t, |reader| deserialize(std::marker::PhantomData, reader));
+function!(from_reader_seed, |t| t, |seed, reader| deserialize(seed, reader));
+
+implement!(Properties, SerdeText);
+implement!(Properties, SerdeBytes);
+implement!(Properties, SerdeStream);
+implement!(Properties, Metadata, ["properties"], ["text/x-java-properties"]);
+
+fn deserialize<'d, S, R>(seed: S, reader: R) -> Result<S::Value, crate::Error>
+where S: DeserializeSeed<'d>, R: Read {
+  let mut document = Sections::default();
+  PropertiesIter::new_with_encoding(reader, UTF_8).read_into(|key, value| {
+    // later entries replace earlier ones with the same key, as in Java
+    match document.general.iter_mut().find(|(existing, _)| *existing == key) {
+      Some(entry) => entry.1 = value,
+      None => document.general.push((key, value))
+    }
+  })?;
+
+  Ok(seed.deserialize(document.deserializer(true))?)
+}
+
+fn write_entry(output: &mut String, key: &str, value: &str) {
+  escape(output, key, true);
+  output.push('=');
+  escape(output, value, false);
+  output.push('\n');
+}
+
+/// Escapes a key or value so that it is read back unchanged.
+fn escape(output: &mut String, text: &str, is_key: bool) {
+  for (index, ch) in text.chars().enumerate() {
+    match ch {
+      '\\' => output.push_str("\\\\"),
+      '\t' => output.push_str("\\t"),
+      '\n' => output.push_str("\\n"),
+      '\r' => output.push_str("\\r"),
+      '\x0c' => output.push_str("\\f"),
+      // spaces end a key, and are trimmed from the start of a value
+      ' ' if is_key || index == 0 => output.push_str("\\ "),
+      // these end a key, or start a comment at the start of a line
+      '=' | ':' | '#' | '!' if is_key => {
+        output.push('\\');
+        output.push(ch);
+      },
+      ch if ch.is_control() => output.push_str(&format!("\\u{:04x}", ch as u32)),
+      ch => output.push(ch)
+    }
+  }
+}
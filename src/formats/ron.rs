@@ -2,31 +2,225 @@
 //!
 //! [`ron`]: https://crates.io/crates/ron
 
-use serde::de::{Deserialize, DeserializeOwned};
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
 use serde::ser::Serialize;
+use serde_ron::extensions::Extensions;
+use serde_ron::ser::PrettyConfig;
+use std::borrow::Cow;
 use std::io::{Read, Write};
 
-use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension};
+use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension, Metadata};
 
-#[derive(Debug, Copy, Clone, Default)]
-pub struct Ron;
+/// RON format options, used when pretty-printing.
+///
+/// The free functions in this module, and `Ron::default()`, pretty-print with two spaces of indentation
+/// and otherwise use the same defaults as [`PrettyConfig`].
+///
+/// [`PrettyConfig`]: https://docs.rs/ron/0.6/ron/ser/struct.PrettyConfig.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ron {
+  depth_limit: usize,
+  new_line: Cow<'static, str>,
+  indentor: Cow<'static, str>,
+  separate_tuple_members: bool,
+  enumerate_arrays: bool,
+  decimal_floats: bool,
+  extensions: Extensions
+}
+
+impl Ron {
+  /// Creates a new `Ron` with the default options.
+  #[inline]
+  pub const fn new() -> Self {
+    Ron {
+      depth_limit: usize::MAX,
+      new_line: Cow::Borrowed(if cfg!(windows) { "\r\n" } else { "\n" }),
+      indentor: Cow::Borrowed("  "),
+      separate_tuple_members: false,
+      enumerate_arrays: false,
+      decimal_floats: false,
+      extensions: Extensions::empty()
+    }
+  }
+
+  /// Sets the depth past which values are no longer pretty-printed.
+  #[inline]
+  pub fn with_depth_limit(mut self, depth_limit: usize) -> Self {
+    self.depth_limit = depth_limit;
+    self
+  }
+
+  /// Sets the string used for line breaks.
+  #[inline]
+  pub fn with_new_line(mut self, new_line: impl Into<Cow<'static, str>>) -> Self {
+    self.new_line = new_line.into();
+    self
+  }
+
+  /// Sets the string used to indent each level.
+  #[inline]
+  pub fn with_indentor(mut self, indentor: impl Into<Cow<'static, str>>) -> Self {
+    self.indentor = indentor.into();
+    self
+  }
+
+  /// Sets whether tuple members are written on separate lines.
+  #[inline]
+  pub fn with_separate_tuple_members(mut self, separate_tuple_members: bool) -> Self {
+    self.separate_tuple_members = separate_tuple_members;
+    self
+  }
+
+  /// Sets whether array elements are annotated with their index in a comment.
+  #[inline]
+  pub fn with_enumerate_arrays(mut self, enumerate_arrays: bool) -> Self {
+    self.enumerate_arrays = enumerate_arrays;
+    self
+  }
+
+  /// Sets whether floats are always written with a decimal point.
+  #[inline]
+  pub fn with_decimal_floats(mut self, decimal_floats: bool) -> Self {
+    self.decimal_floats = decimal_floats;
+    self
+  }
+
+  /// Sets the RON extensions enabled in the output.
+  #[inline]
+  pub fn with_extensions(mut self, extensions: Extensions) -> Self {
+    self.extensions = extensions;
+    self
+  }
+
+  /// Builds the [`PrettyConfig`] described by these options.
+  ///
+  /// [`PrettyConfig`]: https://docs.rs/ron/0.6/ron/ser/struct.PrettyConfig.html
+  pub fn pretty_config(&self) -> PrettyConfig {
+    PrettyConfig::new()
+      .with_depth_limit(self.depth_limit)
+      .with_new_line(self.new_line.clone().into_owned())
+      .with_indentor(self.indentor.clone().into_owned())
+      .with_separate_tuple_members(self.separate_tuple_members)
+      .with_enumerate_arrays(self.enumerate_arrays)
+      .with_decimal_floats(self.decimal_floats)
+      .with_extensions(self.extensions)
+  }
+}
+
+impl Default for Ron {
+  #[inline]
+  fn default() -> Self {
+    Ron::new()
+  }
+}
 
 function!(to_string_pretty, super::map_err, |value| serde_ron::ser::to_string_pretty(value, pretty()));
 function!(to_string, super::map_err, serde_ron::ser::to_string);
 function!(from_str, super::map_err, serde_ron::de::from_str);
+function!(from_str_seed, super::map_err, |seed, data| deserialize_seed(seed, data.as_bytes()));
 function!(to_vec_pretty, super::map_err, |value| serde_ron::ser::to_string_pretty(value, pretty()).map(String::into_bytes));
 function!(to_vec, super::map_err, |value| serde_ron::ser::to_string(value).map(String::into_bytes));
 function!(from_slice, super::map_err, serde_ron::de::from_bytes);
+function!(from_slice_seed, super::map_err, deserialize_seed);
 function!(to_writer_pretty, super::map_err, |writer, value| serde_ron::ser::to_writer_pretty(writer, value, pretty()));
 function!(to_writer, super::map_err, serde_ron::ser::to_writer);
 function!(from_reader, super::map_err, serde_ron::de::from_reader);
+function!(from_reader_seed, |t| t, |seed, reader| {
+  let mut data = Vec::new();
+  {reader}.read_to_end(&mut data)?;
+  Ok(deserialize_seed(seed, &data)?)
+});
+
+impl SerdeText for Ron {
+  #[inline]
+  fn to_string_pretty<T>(&self, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    serde_ron::ser::to_string_pretty(value, self.pretty_config()).map_err(super::map_err)
+  }
+
+  #[inline]
+  fn to_string<T>(&self, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    to_string(value)
+  }
+
+  #[inline]
+  fn from_str<'d, T>(&self, data: &'d str) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_str(data)
+  }
 
-implement!(Ron, SerdeTextPretty);
-implement!(Ron, SerdeBytesPretty);
-implement!(Ron, SerdeStreamPretty);
-implement!(Ron, Extension, "ron");
+  #[inline]
+  fn from_str_seed<'d, S>(&self, seed: S, data: &'d str) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_str_seed(seed, data)
+  }
+}
+
+impl SerdeBytes for Ron {
+  #[inline]
+  fn to_vec_pretty<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    self.to_string_pretty(value).map(String::into_bytes)
+  }
+
+  #[inline]
+  fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    to_vec(value)
+  }
+
+  #[inline]
+  fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_slice(data)
+  }
+
+  #[inline]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_slice_seed(seed, data)
+  }
+}
+
+impl SerdeStream for Ron {
+  #[inline]
+  fn to_writer_pretty<W, T>(&self, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    serde_ron::ser::to_writer_pretty(writer, value, self.pretty_config()).map_err(super::map_err)
+  }
+
+  #[inline]
+  fn to_writer<W, T>(&self, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    to_writer(writer, value)
+  }
+
+  #[inline]
+  fn from_reader<R, T>(&self, reader: R) -> Result<T, crate::Error>
+  where R: Read, T: DeserializeOwned {
+    from_reader(reader)
+  }
+
+  #[inline]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    from_reader_seed(seed, reader)
+  }
+}
+
+implement!(Ron, Metadata, ["ron"], ["application/x-ron"]);
+
+#[inline]
+fn pretty() -> PrettyConfig {
+  Ron::new().pretty_config()
+}
 
 #[inline]
-fn pretty() -> serde_ron::ser::PrettyConfig {
-  serde_ron::ser::PrettyConfig::new().with_indentor("  ".to_owned())
+fn deserialize_seed<'d, S>(seed: S, data: &'d [u8]) -> serde_ron::Result<S::Value>
+where S: DeserializeSeed<'d> {
+  let mut deserializer = serde_ron::de::Deserializer::from_bytes(data)?;
+  let value = seed.deserialize(&mut deserializer)?;
+  deserializer.end()?;
+  Ok(value)
 }
@@ -0,0 +1,430 @@
+//! The document model shared by INI and `.properties` files:
+//! string values, optionally grouped into a single level of named sections.
+
+use serde::de::{Deserializer, Visitor};
+use serde::de::value::{Error as DeError, MapDeserializer};
+use serde::ser::{self, Error as _, Impossible, Serialize, Serializer};
+use std::fmt::Display;
+
+use super::field::FieldDeserializer;
+
+/// A document of string values, some of which are grouped into named sections.
+#[derive(Debug, Default)]
+pub(super) struct Sections {
+  /// Values that are not in any section.
+  pub(super) general: Vec<(String, String)>,
+  /// Named sections, in the order they were serialized or read.
+  pub(super) sections: Vec<(String, Vec<(String, String)>)>
+}
+
+impl Sections {
+  /// Serializes a map or struct into a document, where any values that are themselves maps or structs become sections.
+  /// `format` names the format in error messages.
+  pub(super) fn serialize<T>(format: &'static str, value: &T) -> Result<Self, DeError>
+  where T: Serialize + ?Sized {
+    let mut document = Sections::default();
+    if let Some(Value::Section(entries)) = value.serialize(ValueSerializer { format, path: String::new(), depth: 0 })? {
+      for (key, value) in entries {
+        match value {
+          Value::Field(value) => document.general.push((key, value)),
+          Value::Section(entries) => document.sections.push((key, entries.into_iter().filter_map(|(key, value)| match value {
+            Value::Field(value) => Some((key, value)),
+            // sections within sections are rejected while serializing
+            Value::Section(_) => None
+          }).collect()))
+        }
+      }
+    }
+
+    Ok(document)
+  }
+
+  /// Creates a deserializer that reads this document as a map of its general values and sections.
+  ///
+  /// If `dotted` is set, general values with keys like `section.key` are grouped into sections
+  /// when deserializing a struct that has a `section` field but no `section.key` field.
+  #[inline]
+  pub(super) fn deserializer(&self, dotted: bool) -> SectionsDeserializer<'_> {
+    SectionsDeserializer { document: self, dotted }
+  }
+}
+
+/// A serialized value, before it is sorted into general values and sections.
+enum Value {
+  Field(String),
+  Section(Vec<(String, Value)>)
+}
+
+/// Serializes a value at a given depth: the document itself, an entry in the document, or an entry in a section.
+struct ValueSerializer {
+  format: &'static str,
+  path: String,
+  depth: usize
+}
+
+/// The depth of values inside sections, which can no longer be maps or structs.
+const SECTION_DEPTH: usize = 2;
+
+impl ValueSerializer {
+  #[inline]
+  fn field(self, value: impl Display) -> Result<Option<Value>, DeError> {
+    match self.depth {
+      0 => Err(self.too_deep()),
+      _ => Ok(Some(Value::Field(value.to_string())))
+    }
+  }
+
+  #[inline]
+  fn too_deep(&self) -> DeError {
+    match self.depth {
+      0 => DeError::custom(format_args!("{} can only serialize a map or struct at the top level", self.format)),
+      _ => DeError::custom(format_args!("`{}` is nested too deeply, {} can only group values into one level of sections", self.path, self.format))
+    }
+  }
+}
+
+macro_rules! serialize_field {
+  ($($method:ident($type:ty);)*) => {
+    $(
+      #[inline]
+      fn $method(self, v: $type) -> Result<Self::Ok, Self::Error> {
+        self.field(v)
+      }
+    )*
+  };
+}
+
+macro_rules! serialize_too_deep {
+  ($($method:ident($($arg:ident: $type:ty),*) -> $ok:ty;)*) => {
+    $(
+      #[inline]
+      fn $method(self, $(_: $type),*) -> Result<$ok, Self::Error> {
+        Err(self.too_deep())
+      }
+    )*
+  };
+}
+
+impl Serializer for ValueSerializer {
+  type Ok = Option<Value>;
+  type Error = DeError;
+  type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+  type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+  type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+  type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+  type SerializeMap = SectionSerializer;
+  type SerializeStruct = SectionSerializer;
+  type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+  serialize_field! {
+    serialize_bool(bool);
+    serialize_i8(i8);
+    serialize_i16(i16);
+    serialize_i32(i32);
+    serialize_i64(i64);
+    serialize_i128(i128);
+    serialize_u8(u8);
+    serialize_u16(u16);
+    serialize_u32(u32);
+    serialize_u64(u64);
+    serialize_u128(u128);
+    serialize_f32(f32);
+    serialize_f64(f64);
+    serialize_char(char);
+    serialize_str(&str);
+  }
+
+  #[inline]
+  fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+    match std::str::from_utf8(v) {
+      Ok(v) => self.field(v),
+      Err(_) => Err(DeError::custom(format_args!("{} can only serialize byte buffers that are valid UTF-8", self.format)))
+    }
+  }
+
+  #[inline]
+  fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+    match self.depth {
+      0 => Err(self.too_deep()),
+      _ => Ok(None)
+    }
+  }
+
+  #[inline]
+  fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+  where T: Serialize + ?Sized {
+    value.serialize(self)
+  }
+
+  #[inline]
+  fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+    self.field("")
+  }
+
+  #[inline]
+  fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+    self.field("")
+  }
+
+  #[inline]
+  fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<Self::Ok, Self::Error> {
+    self.field(variant)
+  }
+
+  #[inline]
+  fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+  where T: Serialize + ?Sized {
+    value.serialize(self)
+  }
+
+  #[inline]
+  fn serialize_newtype_variant<T>(self, _: &'static str, _: u32, _: &'static str, _: &T) -> Result<Self::Ok, Self::Error>
+  where T: Serialize + ?Sized {
+    Err(self.too_deep())
+  }
+
+  #[inline]
+  fn serialize_map(self, _: Option<usize>) -> Result<SectionSerializer, Self::Error> {
+    match self.depth {
+      SECTION_DEPTH => Err(self.too_deep()),
+      _ => Ok(SectionSerializer { serializer: self, entries: Vec::new(), key: None })
+    }
+  }
+
+  #[inline]
+  fn serialize_struct(self, _: &'static str, len: usize) -> Result<SectionSerializer, Self::Error> {
+    self.serialize_map(Some(len))
+  }
+
+  serialize_too_deep! {
+    serialize_seq(len: Option<usize>) -> Self::SerializeSeq;
+    serialize_tuple(len: usize) -> Self::SerializeTuple;
+    serialize_tuple_struct(name: &'static str, len: usize) -> Self::SerializeTupleStruct;
+    serialize_tuple_variant(name: &'static str, index: u32, variant: &'static str, len: usize) -> Self::SerializeTupleVariant;
+    serialize_struct_variant(name: &'static str, index: u32, variant: &'static str, len: usize) -> Self::SerializeStructVariant;
+  }
+}
+
+/// Serializes the entries of the document, or of one of its sections.
+struct SectionSerializer {
+  serializer: ValueSerializer,
+  entries: Vec<(String, Value)>,
+  key: Option<String>
+}
+
+impl SectionSerializer {
+  fn entry<T>(&mut self, key: String, value: &T) -> Result<(), DeError>
+  where T: Serialize + ?Sized {
+    let path = match self.serializer.depth {
+      0 => key.clone(),
+      _ => format!("{}.{}", self.serializer.path, key)
+    };
+
+    let serializer = ValueSerializer { format: self.serializer.format, path, depth: self.serializer.depth + 1 };
+    if let Some(value) = value.serialize(serializer)? {
+      self.entries.push((key, value));
+    }
+
+    Ok(())
+  }
+}
+
+impl ser::SerializeMap for SectionSerializer {
+  type Ok = Option<Value>;
+  type Error = DeError;
+
+  fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+  where T: Serialize + ?Sized {
+    let serializer = ValueSerializer { format: self.serializer.format, path: self.serializer.path.clone(), depth: SECTION_DEPTH };
+    match key.serialize(serializer)? {
+      Some(Value::Field(key)) => self.key = Some(key),
+      _ => return Err(DeError::custom(format_args!("{} can only serialize maps with string keys", self.serializer.format)))
+    }
+
+    Ok(())
+  }
+
+  #[inline]
+  fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+  where T: Serialize + ?Sized {
+    let key = self.key.take().expect("`serialize_value` called before `serialize_key`");
+    self.entry(key, value)
+  }
+
+  #[inline]
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    Ok(Some(Value::Section(self.entries)))
+  }
+}
+
+impl ser::SerializeStruct for SectionSerializer {
+  type Ok = Option<Value>;
+  type Error = DeError;
+
+  #[inline]
+  fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+  where T: Serialize + ?Sized {
+    self.entry(key.to_owned(), value)
+  }
+
+  #[inline]
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    Ok(Some(Value::Section(self.entries)))
+  }
+}
+
+/// Deserializes a document as a map of its general values and sections.
+pub(super) struct SectionsDeserializer<'a> {
+  document: &'a Sections,
+  dotted: bool
+}
+
+impl<'a> SectionsDeserializer<'a> {
+  /// Lists the entries of the document, grouping dotted keys into sections if enabled and expected by `fields`.
+  fn entries(&self, fields: &[&str]) -> Vec<(FieldDeserializer<'a>, EntryDeserializer<'a>)> {
+    let mut entries = Vec::new();
+    let mut sections: Vec<(&'a str, Vec<(&'a str, &'a str)>)> = Vec::new();
+    for (key, value) in &self.document.general {
+      match key.split_once('.') {
+        Some((section, field)) if self.dotted && fields.contains(&section) && !fields.contains(&key.as_str()) => {
+          match sections.iter_mut().find(|(name, _)| *name == section) {
+            Some((_, entries)) => entries.push((field, value)),
+            None => sections.push((section, vec![(field, value)]))
+          }
+        },
+        _ => entries.push((FieldDeserializer(key), EntryDeserializer::Field(FieldDeserializer(value))))
+      }
+    }
+
+    sections.extend(self.document.sections.iter().map(|(name, entries)| {
+      (name.as_str(), entries.iter().map(|(key, value)| (key.as_str(), value.as_str())).collect())
+    }));
+    entries.extend(sections.into_iter().map(|(name, entries)| (FieldDeserializer(name), EntryDeserializer::Section(entries))));
+    entries
+  }
+}
+
+impl<'a, 'd> Deserializer<'d> for SectionsDeserializer<'a> {
+  type Error = DeError;
+
+  #[inline]
+  fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_map(MapDeserializer::new(self.entries(&[]).into_iter()))
+  }
+
+  #[inline]
+  fn deserialize_struct<V>(self, _: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_map(MapDeserializer::new(self.entries(fields).into_iter()))
+  }
+
+  #[inline]
+  fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_newtype_struct(self)
+  }
+
+  #[inline]
+  fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_some(self)
+  }
+
+  serde::forward_to_deserialize_any! {
+    <V: Visitor<'d>>
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+    bytes byte_buf unit unit_struct seq tuple tuple_struct map enum identifier ignored_any
+  }
+}
+
+/// Deserializes an entry in the document, which is either a single value or a section.
+enum EntryDeserializer<'a> {
+  Field(FieldDeserializer<'a>),
+  Section(Vec<(&'a str, &'a str)>)
+}
+
+macro_rules! forward_field {
+  ($($method:ident($($arg:ident: $type:ty),*);)*) => {
+    $(
+      #[inline]
+      fn $method<V>(self, $($arg: $type,)* visitor: V) -> Result<V::Value, Self::Error>
+      where V: Visitor<'d> {
+        match self {
+          EntryDeserializer::Field(field) => field.$method($($arg,)* visitor),
+          section => section.deserialize_any(visitor)
+        }
+      }
+    )*
+  };
+}
+
+impl<'a, 'd> Deserializer<'d> for EntryDeserializer<'a> {
+  type Error = DeError;
+
+  fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    match self {
+      EntryDeserializer::Field(field) => field.deserialize_any(visitor),
+      EntryDeserializer::Section(entries) => {
+        let entries = entries.into_iter().map(|(key, value)| (FieldDeserializer(key), FieldDeserializer(value)));
+        visitor.visit_map(MapDeserializer::new(entries))
+      }
+    }
+  }
+
+  #[inline]
+  fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    match self {
+      EntryDeserializer::Field(field) => field.deserialize_option(visitor),
+      section => visitor.visit_some(section)
+    }
+  }
+
+  #[inline]
+  fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_newtype_struct(self)
+  }
+
+  forward_field! {
+    deserialize_bool();
+    deserialize_i8();
+    deserialize_i16();
+    deserialize_i32();
+    deserialize_i64();
+    deserialize_i128();
+    deserialize_u8();
+    deserialize_u16();
+    deserialize_u32();
+    deserialize_u64();
+    deserialize_u128();
+    deserialize_f32();
+    deserialize_f64();
+    deserialize_char();
+    deserialize_str();
+    deserialize_string();
+    deserialize_bytes();
+    deserialize_byte_buf();
+    deserialize_unit();
+    deserialize_unit_struct(name: &'static str);
+    deserialize_seq();
+    deserialize_tuple(len: usize);
+    deserialize_tuple_struct(name: &'static str, len: usize);
+    deserialize_map();
+    deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+    deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+    deserialize_identifier();
+    deserialize_ignored_any();
+  }
+}
+
+impl<'a, 'd> serde::de::IntoDeserializer<'d, DeError> for EntryDeserializer<'a> {
+  type Deserializer = Self;
+
+  #[inline]
+  fn into_deserializer(self) -> Self {
+    self
+  }
+}
@@ -0,0 +1,281 @@
+//! S-expression serialization/deserialization, via the [`serde-lexpr`] crate.
+//!
+//! Values use the representation described by [`serde-lexpr`]: sequences are lists, tuples are vectors,
+//! structs and maps are association lists such as `((name . "value"))`, and `Option`s are lists
+//! of zero or one elements.
+//!
+//! [`serde-lexpr`]: https://crates.io/crates/serde-lexpr
+
+use serde::de::{self, Deserialize, DeserializeOwned, DeserializeSeed, Deserializer, Error as _, IntoDeserializer, Unexpected, Visitor};
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::ser::Serialize;
+use serde_lexpr::{parse, print, Cons, Value};
+use std::io::{Read, Write};
+
+use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension, Metadata};
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct SExpr;
+
+function!(to_string_pretty, |t| t, |value| {
+  let mut output = String::new();
+  pretty(&mut output, &serde_lexpr::to_value(value)?, 0)?;
+  Ok(output)
+});
+function!(to_string, super::map_err, serde_lexpr::to_string);
+function!(from_str, |t| t, |data| from_str_seed(std::marker::PhantomData, data));
+function!(from_str_seed, |t| t, |seed, data| Ok(seed.deserialize(ValueDeserializer(&parse::from_str(data)?))?));
+function!(to_vec_pretty, |t| t, |value| to_string_pretty(value).map(String::into_bytes));
+function!(to_vec, super::map_err, serde_lexpr::to_vec);
+function!(from_slice, |t| t, |data| from_slice_seed(std::marker::PhantomData, data));
+function!(from_slice_seed, |t| t, |seed, data| Ok(seed.deserialize(ValueDeserializer(&parse::from_slice(data)?))?));
+function!(to_writer_pretty, |t| t, |writer, value| {
+  {writer}.write_all(to_string_pretty(value)?.as_bytes())?;
+  Ok(())
+});
+function!(to_writer, super::map_err, |writer, value| serde_lexpr::to_writer(writer, value));
+function!(from_reader, |t| t, |reader| from_reader_seed(std::marker::PhantomData, reader));
+function!(from_reader_seed, |t| t, |seed, reader| Ok(seed.deserialize(ValueDeserializer(&parse::from_reader(reader)?))?));
+
+implement!(SExpr, SerdeTextPretty);
+implement!(SExpr, SerdeBytesPretty);
+implement!(SExpr, SerdeStreamPretty);
+implement!(SExpr, Metadata, ["sexp", "sexpr"], ["text/x-sexpr"]);
+
+/// Pretty-prints a value, putting each element of a list on its own line if any of them are lists themselves.
+/// Elements are aligned with the first element of their list, and the value of a pair stays on the line of its key.
+fn pretty(output: &mut String, value: &Value, column: usize) -> Result<(), crate::Error> {
+  let (open, elements, tail) = match value {
+    Value::Cons(cons) => {
+      let (elements, tail) = cons.to_ref_vec();
+      ("(", elements, Some(tail).filter(|tail| !tail.is_null()))
+    },
+    Value::Vector(elements) => ("#(", elements.iter().collect(), None),
+    atom => {
+      output.push_str(&print::to_string(atom)?);
+      return Ok(());
+    }
+  };
+
+  if elements.iter().copied().chain(tail).all(|element| !element.is_cons() && !element.is_vector()) {
+    output.push_str(&print::to_string(value)?);
+    return Ok(());
+  }
+
+  let column = column + open.len();
+  let single = elements.len() == 1;
+  output.push_str(open);
+  for (index, element) in elements.into_iter().enumerate() {
+    if index > 0 {
+      newline(output, column);
+    }
+
+    pretty(output, element, column)?;
+  }
+
+  if let Some(tail) = tail {
+    // pairs such as `(key . value)` keep the value on the same line as the key
+    if single {
+      output.push(' ');
+    } else {
+      newline(output, column);
+    }
+
+    output.push_str(". ");
+    let column = output.rsplit('\n').next().unwrap_or_default().chars().count();
+    pretty(output, tail, column)?;
+  }
+
+  output.push(')');
+  Ok(())
+}
+
+#[inline]
+fn newline(output: &mut String, column: usize) {
+  output.push('\n');
+  output.push_str(&" ".repeat(column));
+}
+
+/// Deserializes a parsed value, reading the same representation that [`serde-lexpr`] serializes.
+///
+/// [`serde-lexpr`]: https://crates.io/crates/serde-lexpr
+struct ValueDeserializer<'a>(&'a Value);
+
+impl<'a> ValueDeserializer<'a> {
+  #[inline]
+  fn invalid_type(&self, expected: &str) -> serde_lexpr::Error {
+    let unexpected = match self.0 {
+      Value::Nil => Unexpected::Other("nil"),
+      Value::Null => Unexpected::Other("empty list"),
+      Value::Bool(value) => Unexpected::Bool(*value),
+      Value::Number(_) => Unexpected::Other("number"),
+      Value::Char(value) => Unexpected::Char(*value),
+      Value::String(value) => Unexpected::Str(value),
+      Value::Symbol(_) => Unexpected::Other("symbol"),
+      Value::Keyword(_) => Unexpected::Other("keyword"),
+      Value::Bytes(value) => Unexpected::Bytes(value),
+      Value::Cons(_) => Unexpected::Other("list"),
+      Value::Vector(_) => Unexpected::Other("vector")
+    };
+
+    serde_lexpr::Error::invalid_type(unexpected, &expected)
+  }
+
+  /// The elements of a list or vector. Dotted pairs are treated as two-element lists,
+  /// but longer improper lists such as `(a b . c)` are rejected.
+  fn elements(&self) -> Result<Vec<&'a Value>, serde_lexpr::Error> {
+    match self.0 {
+      Value::Null => Ok(Vec::new()),
+      Value::Cons(cons) => match cons.to_ref_vec() {
+        (elements, Value::Null) => Ok(elements),
+        (elements, _) if elements.len() == 1 => Ok(vec![cons.car(), cons.cdr()]),
+        (_, _) => Err(serde_lexpr::Error::invalid_value(Unexpected::Other("improper list"), &"a proper list or a pair"))
+      },
+      Value::Vector(elements) => Ok(elements.iter().collect()),
+      _ => Err(self.invalid_type("a list"))
+    }
+  }
+}
+
+impl<'a, 'd> Deserializer<'d> for ValueDeserializer<'a> {
+  type Error = serde_lexpr::Error;
+
+  fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    match self.0 {
+      Value::Nil => visitor.visit_unit(),
+      Value::Bool(value) => visitor.visit_bool(*value),
+      Value::Number(number) => match (number.as_u64(), number.as_i64(), number.as_f64()) {
+        (Some(value), _, _) => visitor.visit_u64(value),
+        (None, Some(value), _) => visitor.visit_i64(value),
+        (None, None, Some(value)) => visitor.visit_f64(value),
+        (None, None, None) => Err(self.invalid_type("a number that fits in 64 bits"))
+      },
+      Value::Char(value) => visitor.visit_char(*value),
+      Value::String(value) | Value::Symbol(value) | Value::Keyword(value) => visitor.visit_str(value),
+      Value::Bytes(value) => visitor.visit_bytes(value),
+      Value::Null | Value::Cons(_) | Value::Vector(_) => visitor.visit_seq(SeqDeserializer::new(self.elements()?.into_iter().map(ValueDeserializer)))
+    }
+  }
+
+  #[inline]
+  fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    match self.0 {
+      Value::Null => visitor.visit_none(),
+      Value::Cons(cons) if cons.cdr().is_null() => visitor.visit_some(ValueDeserializer(cons.car())),
+      _ => Err(self.invalid_type("an empty or one-element list"))
+    }
+  }
+
+  #[inline]
+  fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    match self.0 {
+      Value::Nil | Value::Null => visitor.visit_unit(),
+      _ => Err(self.invalid_type("nil or an empty list"))
+    }
+  }
+
+  #[inline]
+  fn deserialize_unit_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    self.deserialize_unit(visitor)
+  }
+
+  #[inline]
+  fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_newtype_struct(self)
+  }
+
+  fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    let entries = match self.0 {
+      Value::Null => Vec::new(),
+      Value::Cons(cons) => cons.list_iter()
+        .map(|entry| entry.as_pair().ok_or_else(|| ValueDeserializer(entry).invalid_type("a pair")))
+        .collect::<Result<Vec<_>, _>>()?,
+      _ => return Err(self.invalid_type("an association list"))
+    };
+
+    visitor.visit_map(MapDeserializer::new(entries.into_iter().map(|(key, value)| (ValueDeserializer(key), ValueDeserializer(value)))))
+  }
+
+  #[inline]
+  fn deserialize_struct<V>(self, _: &'static str, _: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    self.deserialize_map(visitor)
+  }
+
+  fn deserialize_enum<V>(self, _: &'static str, _: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    match self.0 {
+      Value::Symbol(variant) => visitor.visit_enum(IntoDeserializer::<Self::Error>::into_deserializer(&**variant)),
+      Value::Cons(cons) => visitor.visit_enum(VariantDeserializer(cons)),
+      _ => Err(self.invalid_type("a symbol or a list starting with one"))
+    }
+  }
+
+  #[inline]
+  fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    visitor.visit_unit()
+  }
+
+  serde::forward_to_deserialize_any! {
+    <V: Visitor<'d>>
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+    bytes byte_buf seq tuple tuple_struct identifier
+  }
+}
+
+impl<'a, 'd> IntoDeserializer<'d, serde_lexpr::Error> for ValueDeserializer<'a> {
+  type Deserializer = Self;
+
+  #[inline]
+  fn into_deserializer(self) -> Self {
+    self
+  }
+}
+
+/// Deserializes an enum variant with data, which is a list starting with the name of the variant.
+struct VariantDeserializer<'a>(&'a Cons);
+
+impl<'a, 'd> de::EnumAccess<'d> for VariantDeserializer<'a> {
+  type Error = serde_lexpr::Error;
+  type Variant = Self;
+
+  #[inline]
+  fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self), Self::Error>
+  where S: DeserializeSeed<'d> {
+    Ok((seed.deserialize(ValueDeserializer(self.0.car()))?, self))
+  }
+}
+
+impl<'a, 'd> de::VariantAccess<'d> for VariantDeserializer<'a> {
+  type Error = serde_lexpr::Error;
+
+  #[inline]
+  fn unit_variant(self) -> Result<(), Self::Error> {
+    ValueDeserializer(self.0.cdr()).deserialize_unit(de::IgnoredAny).map(drop)
+  }
+
+  #[inline]
+  fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, Self::Error>
+  where S: DeserializeSeed<'d> {
+    seed.deserialize(ValueDeserializer(self.0.cdr()))
+  }
+
+  #[inline]
+  fn tuple_variant<V>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    ValueDeserializer(self.0.cdr()).deserialize_seq(visitor)
+  }
+
+  #[inline]
+  fn struct_variant<V>(self, _: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+  where V: Visitor<'d> {
+    ValueDeserializer(self.0.cdr()).deserialize_map(visitor)
+  }
+}
@@ -6,21 +6,91 @@
 //! [`toml`]: https://crates.io/crates/toml
 //! [`SerdeStream`]: ../../traits/trait.SerdeStream.html
 
-use serde::de::{Deserialize, DeserializeOwned};
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
 use serde::ser::Serialize;
 use std::io::{Read, Write};
 
-use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension};
+use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension, Metadata};
 
-#[derive(Debug, Copy, Clone, Default)]
-pub struct Toml;
+/// TOML format options, used when pretty-printing.
+///
+/// The free functions in this module, and `Toml::default()`, use the same pretty-printing
+/// defaults as [`toml::to_string_pretty`].
+///
+/// [`toml::to_string_pretty`]: https://docs.rs/toml/0.5/toml/fn.to_string_pretty.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Toml {
+  array_style: ArrayStyle,
+  pretty_strings: bool
+}
+
+/// How arrays are laid out when pretty-printing TOML.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ArrayStyle {
+  /// Arrays are written on a single line.
+  Inline,
+  /// Arrays with more than one element are written with one element per line.
+  Multiline {
+    /// The number of spaces each element is indented by.
+    indent: usize,
+    /// Whether the last element is followed by a comma.
+    trailing_comma: bool
+  }
+}
+
+impl Toml {
+  /// Creates a new `Toml` with the default options.
+  #[inline]
+  pub const fn new() -> Self {
+    Toml {
+      array_style: ArrayStyle::Multiline { indent: 4, trailing_comma: true },
+      pretty_strings: true
+    }
+  }
+
+  /// Sets how arrays are laid out.
+  #[inline]
+  pub fn with_array_style(mut self, array_style: ArrayStyle) -> Self {
+    self.array_style = array_style;
+    self
+  }
+
+  /// Sets whether strings are written as literal strings where possible,
+  /// and as multi-line strings when they contain line breaks.
+  #[inline]
+  pub fn with_pretty_strings(mut self, pretty_strings: bool) -> Self {
+    self.pretty_strings = pretty_strings;
+    self
+  }
+
+  /// How arrays are laid out.
+  #[inline]
+  pub fn array_style(&self) -> ArrayStyle {
+    self.array_style
+  }
+
+  /// Whether strings are written as literal or multi-line strings where possible.
+  #[inline]
+  pub fn pretty_strings(&self) -> bool {
+    self.pretty_strings
+  }
+}
+
+impl Default for Toml {
+  #[inline]
+  fn default() -> Self {
+    Toml::new()
+  }
+}
 
 function!(to_string_pretty, super::map_err, serde_toml::to_string_pretty);
 function!(to_string, super::map_err, serde_toml::to_string);
 function!(from_str, super::map_err, |data| serde_toml::from_str::<serde_toml::Value>(data)?.try_into());
+function!(from_str_seed, super::map_err, |seed, data| seed.deserialize(serde_toml::from_str::<serde_toml::Value>(data)?));
 function!(to_vec_pretty, super::map_err, |value| serde_toml::to_string_pretty(value).map(String::into_bytes));
 function!(to_vec, super::map_err, serde_toml::to_vec);
 function!(from_slice, super::map_err, |data| serde_toml::from_slice::<serde_toml::Value>(data)?.try_into());
+function!(from_slice_seed, super::map_err, |seed, data| seed.deserialize(serde_toml::from_slice::<serde_toml::Value>(data)?));
 function!(to_writer_pretty, |t| t, |writer, value| {
   let value = serde_toml::to_string_pretty(value)?;
   {writer}.write_all(value.as_bytes())?;
@@ -36,8 +106,100 @@ function!(from_reader, |t| t, |reader| {
   {reader}.read_to_end(&mut data)?;
   Ok(serde_toml::from_slice::<serde_toml::Value>(&data)?.try_into()?)
 });
+function!(from_reader_seed, |t| t, |seed, reader| {
+  let mut data = Vec::new();
+  {reader}.read_to_end(&mut data)?;
+  Ok(seed.deserialize(serde_toml::from_slice::<serde_toml::Value>(&data)?)?)
+});
+
+impl SerdeText for Toml {
+  fn to_string_pretty<T>(&self, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    let mut data = String::with_capacity(128);
+    let mut serializer = serde_toml::Serializer::pretty(&mut data);
+    serializer.pretty_string(self.pretty_strings);
+    match self.array_style {
+      ArrayStyle::Inline => serializer.pretty_array(false),
+      ArrayStyle::Multiline { indent, trailing_comma } => serializer
+        .pretty_array_indent(indent)
+        .pretty_array_trailing_comma(trailing_comma)
+    };
+
+    value.serialize(&mut serializer)?;
+    Ok(data)
+  }
+
+  #[inline]
+  fn to_string<T>(&self, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    to_string(value)
+  }
+
+  #[inline]
+  fn from_str<'d, T>(&self, data: &'d str) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_str(data)
+  }
+
+  #[inline]
+  fn from_str_seed<'d, S>(&self, seed: S, data: &'d str) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_str_seed(seed, data)
+  }
+}
+
+impl SerdeBytes for Toml {
+  #[inline]
+  fn to_vec_pretty<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    self.to_string_pretty(value).map(String::into_bytes)
+  }
+
+  #[inline]
+  fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    to_vec(value)
+  }
+
+  #[inline]
+  fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_slice(data)
+  }
+
+  #[inline]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_slice_seed(seed, data)
+  }
+}
+
+impl SerdeStream for Toml {
+  #[inline]
+  fn to_writer_pretty<W, T>(&self, mut writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    let value = self.to_string_pretty(value)?;
+    writer.write_all(value.as_bytes())?;
+    Ok(())
+  }
+
+  #[inline]
+  fn to_writer<W, T>(&self, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    to_writer(writer, value)
+  }
+
+  #[inline]
+  fn from_reader<R, T>(&self, reader: R) -> Result<T, crate::Error>
+  where R: Read, T: DeserializeOwned {
+    from_reader(reader)
+  }
+
+  #[inline]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    from_reader_seed(seed, reader)
+  }
+}
 
-implement!(Toml, SerdeTextPretty);
-implement!(Toml, SerdeBytesPretty);
-implement!(Toml, SerdeStreamPretty);
-implement!(Toml, Extension, "toml");
+implement!(Toml, Metadata, ["toml"], ["application/toml"]);
@@ -0,0 +1,42 @@
+//! URL-encoded form and query string serialization/deserialization, via the [`serde_urlencoded`] crate.
+//!
+//! Only flat maps and structs can be represented, with every value being
+//! a string, number, boolean, unit or `Option` of one of those.
+//!
+//! [`serde_urlencoded`]: https://crates.io/crates/serde_urlencoded
+
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
+use serde::ser::Serialize;
+use std::io::{Read, Write};
+
+use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension, Metadata};
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct UrlEncoded;
+
+function!(to_string, super::map_err, serde_urlencoded::to_string);
+function!(from_str, super::map_err, serde_urlencoded::from_str);
+function!(from_str_seed, super::map_err, |seed, data| seed.deserialize(deserializer(data.as_bytes())));
+function!(to_vec, super::map_err, |value| serde_urlencoded::to_string(value).map(String::into_bytes));
+function!(from_slice, super::map_err, serde_urlencoded::from_bytes);
+function!(from_slice_seed, super::map_err, |seed, data| seed.deserialize(deserializer(data)));
+function!(to_writer, |t| t, |writer, value| {
+  {writer}.write_all(to_string(value)?.as_bytes())?;
+  Ok(())
+});
+function!(from_reader, super::map_err, serde_urlencoded::from_reader);
+function!(from_reader_seed, |t| t, |seed, reader| {
+  let mut data = Vec::new();
+  {reader}.read_to_end(&mut data)?;
+  Ok(seed.deserialize(deserializer(&data))?)
+});
+
+implement!(UrlEncoded, SerdeText);
+implement!(UrlEncoded, SerdeBytes);
+implement!(UrlEncoded, SerdeStream);
+implement!(UrlEncoded, Metadata, ["urlencoded"], ["application/x-www-form-urlencoded"]);
+
+#[inline]
+fn deserializer(data: &[u8]) -> serde_urlencoded::Deserializer<'_> {
+  serde_urlencoded::Deserializer::new(form_urlencoded::parse(data))
+}
@@ -2,23 +2,280 @@
 //!
 //! [`serde-xml-rs`]: https://crates.io/crates/serde-xml-rs
 
-use serde::de::{Deserialize, DeserializeOwned};
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
 use serde::ser::Serialize;
+use xml_rs::common::XmlVersion;
+use xml_rs::name::Name;
+use xml_rs::reader::EventReader;
+use xml_rs::writer::{EmitterConfig, XmlEvent};
+use std::borrow::Cow;
 use std::io::{Read, Write};
 
-use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension};
+use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension, Metadata};
 
-#[derive(Debug, Copy, Clone, Default)]
-pub struct Xml;
+/// XML format options.
+///
+/// The free functions in this module, and `Xml::default()`, name the root element
+/// after the serialized type, the same as [`serde-xml-rs`] does, write no XML declaration
+/// and pretty-print with two spaces of indentation.
+///
+/// [`serde-xml-rs`]: https://crates.io/crates/serde-xml-rs
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Xml {
+  root: Option<Cow<'static, str>>,
+  namespaces: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+  declaration: bool,
+  encoding: Cow<'static, str>,
+  indent: Cow<'static, str>
+}
 
+impl Xml {
+  /// Creates a new `Xml` with the default options.
+  #[inline]
+  pub const fn new() -> Self {
+    Xml {
+      root: None,
+      namespaces: Vec::new(),
+      declaration: false,
+      encoding: Cow::Borrowed("UTF-8"),
+      indent: Cow::Borrowed("  ")
+    }
+  }
+
+  /// Sets the name of the root element, replacing the name of the serialized type.
+  #[inline]
+  pub fn with_root(mut self, root: impl Into<Cow<'static, str>>) -> Self {
+    self.root = Some(root.into());
+    self
+  }
+
+  /// Declares a namespace on the root element. An empty prefix declares the default namespace.
+  #[inline]
+  pub fn with_namespace(mut self, prefix: impl Into<Cow<'static, str>>, uri: impl Into<Cow<'static, str>>) -> Self {
+    self.namespaces.push((prefix.into(), uri.into()));
+    self
+  }
+
+  /// Sets whether the output starts with an XML declaration, such as `<?xml version="1.0" encoding="UTF-8"?>`.
+  #[inline]
+  pub fn with_declaration(mut self, declaration: bool) -> Self {
+    self.declaration = declaration;
+    self
+  }
+
+  /// Sets the encoding named in the XML declaration.
+  /// This is only a label: the output is always UTF-8, so it must be re-encoded if this is anything else.
+  #[inline]
+  pub fn with_encoding(mut self, encoding: impl Into<Cow<'static, str>>) -> Self {
+    self.encoding = encoding.into();
+    self
+  }
+
+  /// Sets the string used to indent each level of pretty-printed output.
+  #[inline]
+  pub fn with_indent(mut self, indent: impl Into<Cow<'static, str>>) -> Self {
+    self.indent = indent.into();
+    self
+  }
+
+  /// The name of the root element, if it has been overridden.
+  #[inline]
+  pub fn root(&self) -> Option<&str> {
+    self.root.as_deref()
+  }
+
+  /// The namespaces declared on the root element, as `(prefix, uri)` pairs.
+  #[inline]
+  pub fn namespaces(&self) -> impl Iterator<Item = (&str, &str)> {
+    self.namespaces.iter().map(|(prefix, uri)| (prefix.as_ref(), uri.as_ref()))
+  }
+
+  /// Whether the output starts with an XML declaration.
+  #[inline]
+  pub fn declaration(&self) -> bool {
+    self.declaration
+  }
+
+  /// The encoding named in the XML declaration.
+  #[inline]
+  pub fn encoding(&self) -> &str {
+    &self.encoding
+  }
+
+  /// The string used to indent each level of pretty-printed output.
+  #[inline]
+  pub fn indent(&self) -> &str {
+    &self.indent
+  }
+
+  fn serialize<W, T>(&self, writer: W, value: &T, pretty: bool) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    if pretty || self.declaration || self.root.is_some() || !self.namespaces.is_empty() {
+      self.rewrite(&to_vec(value)?, writer, pretty)
+    } else {
+      to_writer(writer, value)
+    }
+  }
+
+  /// Re-emits a document written by [`serde-xml-rs`] with these options applied,
+  /// since it has no options of its own.
+  ///
+  /// [`serde-xml-rs`]: https://crates.io/crates/serde-xml-rs
+  fn rewrite<W: Write>(&self, data: &[u8], writer: W, pretty: bool) -> Result<(), crate::Error> {
+    let mut writer = EmitterConfig::new()
+      .write_document_declaration(false)
+      .perform_indent(pretty)
+      .normalize_empty_elements(false)
+      .indent_string(self.indent.clone())
+      .create_writer(writer);
+    if self.declaration {
+      writer.write(XmlEvent::StartDocument {
+        version: XmlVersion::Version10,
+        encoding: Some(&self.encoding),
+        standalone: None
+      })?;
+    }
+
+    let mut depth = 0usize;
+    let mut empty = false;
+    for event in EventReader::new(data) {
+      let event = event?;
+      let was_empty = std::mem::replace(&mut empty, false);
+      match event.as_writer_event() {
+        Some(XmlEvent::StartDocument { .. }) | None => (),
+        Some(XmlEvent::StartElement { name, attributes, namespace }) if depth == 0 => {
+          let name = self.root.as_deref().map_or(name, Name::from);
+          let mut namespace = namespace.into_owned();
+          for (prefix, uri) in self.namespaces.iter() {
+            namespace.force_put(prefix.as_ref(), uri.as_ref());
+          }
+
+          writer.write(XmlEvent::StartElement { name, attributes, namespace: Cow::Owned(namespace) })?;
+          depth += 1;
+          empty = true;
+        },
+        Some(event @ XmlEvent::StartElement { .. }) => {
+          writer.write(event)?;
+          depth += 1;
+          empty = true;
+        },
+        Some(XmlEvent::EndElement { .. }) => {
+          // empty text keeps the indenter from putting the closing tag of an empty element on its own line
+          if was_empty {
+            writer.write(XmlEvent::characters(""))?;
+          }
+
+          writer.write(XmlEvent::end_element())?;
+          depth -= 1;
+        },
+        Some(event) => writer.write(event)?
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl Default for Xml {
+  #[inline]
+  fn default() -> Self {
+    Xml::new()
+  }
+}
+
+function!(to_string_pretty, |t| t, |value| Xml::new().to_string_pretty(value));
 function!(to_string, super::map_err, serde_xml_rs::to_string);
 function!(from_str, super::map_err, serde_xml_rs::from_str);
+function!(from_str_seed, super::map_err, |seed, data| seed.deserialize(&mut serde_xml_rs::Deserializer::new_from_reader(data.as_bytes())));
+function!(to_vec_pretty, |t| t, |value| Xml::new().to_vec_pretty(value));
 function!(to_vec, super::map_err, |value| serde_xml_rs::to_string(value).map(String::into_bytes));
 function!(from_slice, super::map_err, |data| serde_xml_rs::from_reader(data));
+function!(from_slice_seed, super::map_err, |seed, data| seed.deserialize(&mut serde_xml_rs::Deserializer::new_from_reader(data)));
+function!(to_writer_pretty, |t| t, |writer, value| Xml::new().to_writer_pretty(writer, value));
 function!(to_writer, super::map_err, serde_xml_rs::to_writer);
 function!(from_reader, super::map_err, serde_xml_rs::from_reader);
+function!(from_reader_seed, super::map_err, |seed, reader| seed.deserialize(&mut serde_xml_rs::Deserializer::new_from_reader(reader)));
+
+impl SerdeText for Xml {
+  #[inline]
+  fn to_string_pretty<T>(&self, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    Ok(String::from_utf8(self.to_vec_pretty(value)?)?)
+  }
+
+  #[inline]
+  fn to_string<T>(&self, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    Ok(String::from_utf8(self.to_vec(value)?)?)
+  }
+
+  #[inline]
+  fn from_str<'d, T>(&self, data: &'d str) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_str(data)
+  }
+
+  #[inline]
+  fn from_str_seed<'d, S>(&self, seed: S, data: &'d str) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_str_seed(seed, data)
+  }
+}
+
+impl SerdeBytes for Xml {
+  #[inline]
+  fn to_vec_pretty<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    let mut data = Vec::with_capacity(128);
+    self.serialize(&mut data, value, true)?;
+    Ok(data)
+  }
+
+  #[inline]
+  fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    let mut data = Vec::with_capacity(128);
+    self.serialize(&mut data, value, false)?;
+    Ok(data)
+  }
+
+  #[inline]
+  fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    from_slice(data)
+  }
+
+  #[inline]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_slice_seed(seed, data)
+  }
+}
+
+impl SerdeStream for Xml {
+  #[inline]
+  fn to_writer_pretty<W, T>(&self, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    self.serialize(writer, value, true)
+  }
+
+  #[inline]
+  fn to_writer<W, T>(&self, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    self.serialize(writer, value, false)
+  }
+
+  #[inline]
+  fn from_reader<R, T>(&self, reader: R) -> Result<T, crate::Error>
+  where R: Read, T: DeserializeOwned {
+    from_reader(reader)
+  }
+
+  #[inline]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    from_reader_seed(seed, reader)
+  }
+}
 
-implement!(Xml, SerdeText);
-implement!(Xml, SerdeBytes);
-implement!(Xml, SerdeStream);
-implement!(Xml, Extension, "xml");
+implement!(Xml, Metadata, ["xml"], ["application/xml", "text/xml"]);
@@ -0,0 +1,52 @@
+//! YAML serialization/deserialization, via the [`serde_yaml`] crate.
+//!
+//! YAML streams may contain several documents separated by `---`. The usual functions
+//! only accept a single document, use [`from_str_multi`], [`from_slice_multi`] or
+//! [`from_reader_multi`] to read all of them.
+//!
+//! [`serde_yaml`]: https://crates.io/crates/serde_yaml
+//! [`from_str_multi`]: ./fn.from_str_multi.html
+//! [`from_slice_multi`]: ./fn.from_slice_multi.html
+//! [`from_reader_multi`]: ./fn.from_reader_multi.html
+
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed};
+use serde::ser::Serialize;
+use std::io::{Read, Write};
+
+use crate::traits::{SerdeBytes, SerdeStream, SerdeText, Extension, Metadata};
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Yaml;
+
+function!(to_string, super::map_err, serde_yaml::to_string);
+function!(from_str, super::map_err, serde_yaml::from_str);
+function!(from_str_seed, super::map_err, |seed, data| seed.deserialize(serde_yaml::Deserializer::from_str(data)));
+function!(to_vec, super::map_err, |value| serde_yaml::to_string(value).map(String::into_bytes));
+function!(from_slice, super::map_err, serde_yaml::from_slice);
+function!(from_slice_seed, super::map_err, |seed, data| seed.deserialize(serde_yaml::Deserializer::from_slice(data)));
+function!(to_writer, super::map_err, serde_yaml::to_writer);
+function!(from_reader, super::map_err, serde_yaml::from_reader);
+function!(from_reader_seed, super::map_err, |seed, reader| seed.deserialize(serde_yaml::Deserializer::from_reader(reader)));
+
+implement!(Yaml, SerdeText);
+implement!(Yaml, SerdeBytes);
+implement!(Yaml, SerdeStream);
+implement!(Yaml, Metadata, ["yaml", "yml"], ["application/yaml", "application/x-yaml", "text/yaml"]);
+
+/// Deserializes every document in a `&str` containing a stream of YAML documents.
+pub fn from_str_multi<'d, T>(data: &'d str) -> Result<Vec<T>, crate::Error>
+where T: Deserialize<'d> {
+  serde_yaml::Deserializer::from_str(data).map(T::deserialize).collect::<Result<_, _>>().map_err(super::map_err)
+}
+
+/// Deserializes every document in a `&[u8]` containing a stream of YAML documents.
+pub fn from_slice_multi<'d, T>(data: &'d [u8]) -> Result<Vec<T>, crate::Error>
+where T: Deserialize<'d> {
+  serde_yaml::Deserializer::from_slice(data).map(T::deserialize).collect::<Result<_, _>>().map_err(super::map_err)
+}
+
+/// Deserializes every document in a reader containing a stream of YAML documents.
+pub fn from_reader_multi<R, T>(reader: R) -> Result<Vec<T>, crate::Error>
+where R: Read, T: DeserializeOwned {
+  serde_yaml::Deserializer::from_reader(reader).map(T::deserialize).collect::<Result<_, _>>().map_err(super::map_err)
+}
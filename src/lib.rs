@@ -8,13 +8,26 @@
 //! Each file format is toggled via feature, all of which are disabled by default.
 //!
 //! Currently the only supported file formats are:
+//! - [Bencode]
 //! - [Bincode]
+//! - [BSON]
 //! - [CBOR]
+//! - [CSV]
+//! - [FlexBuffers]
+//! - [INI]
 //! - [JSON]
+//! - [JSON5]
 //! - [MessagePack]
+//! - [Pickle]
+//! - [Property lists]
+//! - [Postcard]
+//! - [Java properties]
 //! - [RON]
+//! - [S-expressions]
 //! - [TOML]
+//! - [URL-encoded]
 //! - [XML]
+//! - [YAML]
 //!
 //! ## Example Usage
 //! ```rust
@@ -27,7 +40,7 @@
 //! value.insert("bar".to_owned(), 3058);
 //!
 //! // `Json` can be swapped out for any value that implements `SerdeText` here
-//! let s = Json.to_string(&value).expect("failed to serialize");
+//! let s = Json::default().to_string(&value).expect("failed to serialize");
 //! println!("serialized: {}", s);
 //! ```
 //!
@@ -35,22 +48,37 @@
 //! [`Format`]: ./multi/enum.Format.html
 //! [`SerdeBytes`]: ./traits/enum.SerdeBytes.html
 //! [`SerdeStream`]: ./traits/enum.SerdeStream.html
+//! [Bencode]: ./formats/bencode/index.html
 //! [Bincode]: ./formats/bincode/index.html
+//! [BSON]: ./formats/bson/index.html
 //! [CBOR]: ./formats/cbor/index.html
+//! [CSV]: ./formats/csv/index.html
+//! [FlexBuffers]: ./formats/flexbuffers/index.html
+//! [INI]: ./formats/ini/index.html
 //! [JSON]: ./formats/json/index.html
+//! [JSON5]: ./formats/json5/index.html
 //! [MessagePack]: ./formats/messagepack/index.html
+//! [Pickle]: ./formats/pickle/index.html
+//! [Property lists]: ./formats/plist/index.html
+//! [Postcard]: ./formats/postcard/index.html
+//! [Java properties]: ./formats/properties/index.html
 //! [RON]: ./formats/ron/index.html
+//! [S-expressions]: ./formats/sexpr/index.html
 //! [TOML]: ./formats/toml/index.html
+//! [URL-encoded]: ./formats/urlencoded/index.html
 //! [XML]: ./formats/xml/index.html
+//! [YAML]: ./formats/yaml/index.html
 
 #[macro_use]
 mod macros;
 
 pub mod formats;
 pub mod multi;
+pub mod registry;
 pub mod traits;
 
-pub use multi::{Format, FormatError};
-pub use traits::{SerdeBytes, SerdeStream, SerdeText};
+pub use multi::{Format, FormatError, ParseFormatError};
+pub use registry::FormatRegistry;
+pub use traits::{DynSerdeBytes, DynSerdeStream, DynSerdeText, SerdeBytes, SerdeStream, SerdeText};
 
 pub type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -12,6 +12,12 @@ macro_rules! implement {
       where T: Deserialize<'d> {
         from_str(data)
       }
+
+      #[inline]
+      fn from_str_seed<'d, S>(&self, seed: S, data: &'d str) -> Result<S::Value, crate::Error>
+      where S: DeserializeSeed<'d> {
+        from_str_seed(seed, data)
+      }
     }
   };
   ($type:ty, SerdeTextPretty) => {
@@ -33,6 +39,12 @@ macro_rules! implement {
       where T: Deserialize<'d> {
         from_str(data)
       }
+
+      #[inline]
+      fn from_str_seed<'d, S>(&self, seed: S, data: &'d str) -> Result<S::Value, crate::Error>
+      where S: DeserializeSeed<'d> {
+        from_str_seed(seed, data)
+      }
     }
   };
   ($type:ty, SerdeBytes) => {
@@ -48,6 +60,12 @@ macro_rules! implement {
       where T: Deserialize<'d> {
         from_slice(data)
       }
+
+      #[inline]
+      fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+      where S: DeserializeSeed<'d> {
+        from_slice_seed(seed, data)
+      }
     }
   };
   ($type:ty, SerdeBytesPretty) => {
@@ -69,6 +87,12 @@ macro_rules! implement {
       where T: Deserialize<'d> {
         from_slice(data)
       }
+
+      #[inline]
+      fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+      where S: DeserializeSeed<'d> {
+        from_slice_seed(seed, data)
+      }
     }
   };
   ($type:ty, SerdeStream) => {
@@ -84,6 +108,12 @@ macro_rules! implement {
       where R: Read, T: DeserializeOwned {
         from_reader(reader)
       }
+
+      #[inline]
+      fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+      where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+        from_reader_seed(seed, reader)
+      }
     }
   };
   ($type:ty, SerdeStreamPretty) => {
@@ -105,6 +135,12 @@ macro_rules! implement {
       where R: Read, T: DeserializeOwned {
         from_reader(reader)
       }
+
+      #[inline]
+      fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+      where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+        from_reader_seed(seed, reader)
+      }
     }
   };
   ($type:ty, Extension, $extension:literal) => {
@@ -117,6 +153,25 @@ macro_rules! implement {
       }
     }
   };
+  ($type:ty, Metadata, [$extension:literal $(, $extensions:literal)*], [$mime_type:literal $(, $mime_types:literal)*]) => {
+    implement!($type, Extension, $extension);
+
+    pub const EXTENSIONS: &[&str] = &[$extension $(, $extensions)*];
+    pub const MIME_TYPE: &str = $mime_type;
+    pub const MIME_TYPES: &[&str] = &[$mime_type $(, $mime_types)*];
+
+    impl Metadata for $type {
+      #[inline]
+      fn extensions(&self) -> &[&str] {
+        EXTENSIONS
+      }
+
+      #[inline]
+      fn mime_types(&self) -> &[&str] {
+        MIME_TYPES
+      }
+    }
+  };
 }
 
 macro_rules! function {
@@ -150,6 +205,16 @@ macro_rules! function {
       $expr.map_err($map_error)
     }
   };
+  (from_str_seed, $map_error:expr, $path:path) => {
+    function!(from_str_seed, $map_error, |seed, data| $path(seed, data));
+  };
+  (from_str_seed, $map_error:expr, |$seed:ident, $data:ident| $expr:expr) => {
+    #[inline]
+    pub fn from_str_seed<'d, S>($seed: S, $data: &'d str) -> Result<S::Value, $crate::Error>
+    where S: DeserializeSeed<'d> {
+      $expr.map_err($map_error)
+    }
+  };
   (to_vec_pretty, $map_error:expr, $path:path) => {
     function!(to_vec_pretty, $map_error, |value| $path(value));
   };
@@ -180,6 +245,16 @@ macro_rules! function {
       $expr.map_err($map_error)
     }
   };
+  (from_slice_seed, $map_error:expr, $path:path) => {
+    function!(from_slice_seed, $map_error, |seed, data| $path(seed, data));
+  };
+  (from_slice_seed, $map_error:expr, |$seed:ident, $data:ident| $expr:expr) => {
+    #[inline]
+    pub fn from_slice_seed<'d, S>($seed: S, $data: &'d [u8]) -> Result<S::Value, $crate::Error>
+    where S: DeserializeSeed<'d> {
+      $expr.map_err($map_error)
+    }
+  };
   (to_writer_pretty, $map_error:expr, $path:path) => {
     function!(to_writer_pretty, $map_error, |writer, value| $path(writer, value));
   };
@@ -210,4 +285,14 @@ macro_rules! function {
       $expr.map_err($map_error)
     }
   };
+  (from_reader_seed, $map_error:expr, $path:path) => {
+    function!(from_reader_seed, $map_error, |seed, reader| $path(seed, reader));
+  };
+  (from_reader_seed, $map_error:expr, |$seed:ident, $reader:ident| $expr:expr) => {
+    #[inline]
+    pub fn from_reader_seed<R, S, V>($seed: S, $reader: R) -> Result<V, $crate::Error>
+    where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+      $expr.map_err($map_error)
+    }
+  };
 }
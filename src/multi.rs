@@ -3,30 +3,95 @@
 //! Note that the functions here return [`FormatError`] instead of the usual [`Error`].
 //! Use these functions if you would like to be able to detect when a feature is unsupported.
 //!
+//! The functions take either a [`Format`], which uses the default options of that format,
+//! or a format struct such as `MessagePack::new().with_struct_encoding(StructEncoding::Map)`,
+//! which keeps its options. Both convert into a [`Configured`].
+//!
 //! [`FormatError`]: ./enum.FormatError.html
+//! [`Format`]: ./enum.Format.html
+//! [`Configured`]: ./enum.Configured.html
 //! [`Error`]: ../type.Error.html
 
-use serde::de::{Deserialize, DeserializeOwned};
-use serde::ser::Serialize;
+use serde::de::{self, Deserialize, DeserializeOwned, DeserializeSeed, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::collections::BTreeSet;
 use std::fmt::{self, Debug, Display};
 use std::io::{Read, Write};
+use std::path::Path;
+use std::str::FromStr;
 
+#[cfg(feature = "bencode")]
+use crate::formats::bencode;
 #[cfg(feature = "bincode")]
 use crate::formats::bincode;
+#[cfg(feature = "bson")]
+use crate::formats::bson;
 #[cfg(feature = "cbor")]
 use crate::formats::cbor;
+#[cfg(feature = "csv")]
+use crate::formats::csv;
+#[cfg(feature = "flexbuffers")]
+use crate::formats::flexbuffers;
+#[cfg(feature = "ini")]
+use crate::formats::ini;
 #[cfg(feature = "json")]
 use crate::formats::json;
+#[cfg(feature = "json5")]
+use crate::formats::json5;
 #[cfg(feature = "messagepack")]
 use crate::formats::messagepack;
+#[cfg(feature = "pickle")]
+use crate::formats::pickle;
+#[cfg(feature = "plist")]
+use crate::formats::plist;
+#[cfg(feature = "postcard")]
+use crate::formats::postcard;
+#[cfg(feature = "properties")]
+use crate::formats::properties;
 #[cfg(feature = "ron")]
 use crate::formats::ron;
+#[cfg(feature = "sexpr")]
+use crate::formats::sexpr;
 #[cfg(feature = "toml")]
 use crate::formats::toml;
+#[cfg(feature = "urlencoded")]
+use crate::formats::urlencoded;
 #[cfg(feature = "xml")]
 use crate::formats::xml;
+#[cfg(feature = "yaml")]
+use crate::formats::yaml;
+
+use crate::traits::{DynSerdeBytes, DynSerdeStream, DynSerdeText, SerdeBytes, SerdeStream, SerdeText};
 
-use crate::traits::{SerdeText, SerdeBytes, SerdeStream};
+mod detect;
+
+pub use self::detect::{detect, detect_ranked, from_slice_auto, Confidence, Undetected};
+
+// Default-configured instances of the configurable formats, borrowed by `Format::as_dyn_*`
+#[cfg(feature = "bincode")]
+static BINCODE: bincode::Bincode = bincode::Bincode::new();
+#[cfg(feature = "cbor")]
+static CBOR: cbor::Cbor = cbor::Cbor::new();
+#[cfg(feature = "csv")]
+static CSV: csv::Csv = csv::Csv::new();
+#[cfg(feature = "json")]
+static JSON: json::Json = json::Json::new();
+#[cfg(feature = "json5")]
+static JSON5: json5::Json5 = json5::Json5::new();
+#[cfg(feature = "messagepack")]
+static MESSAGEPACK: messagepack::MessagePack = messagepack::MessagePack::new();
+#[cfg(feature = "pickle")]
+static PICKLE: pickle::Pickle = pickle::Pickle::new();
+#[cfg(feature = "plist")]
+static PLIST: plist::Plist = plist::Plist::new();
+#[cfg(feature = "postcard")]
+static POSTCARD: postcard::Postcard = postcard::Postcard::new();
+#[cfg(feature = "ron")]
+static RON: ron::Ron = ron::Ron::new();
+#[cfg(feature = "toml")]
+static TOML: toml::Toml = toml::Toml::new();
+#[cfg(feature = "xml")]
+static XML: xml::Xml = xml::Xml::new();
 
 /// Dynamically pick which format data is serialized from or deserialized into.
 ///
@@ -34,108 +99,649 @@ use crate::traits::{SerdeText, SerdeBytes, SerdeStream};
 /// not support it will return a [`FormatError::Unsupported`] `Err`
 /// to indicate that that operation was not supported by the format.
 ///
+/// Formats are serialized as their lowercase name, such as `"json"` or `"messagepack"`,
+/// and can be deserialized from any name accepted by [`Format::from_str`].
+///
 /// [`FormatError::Unsupported`]: ./enum.FormatError.html#variant.Unsupported
+/// [`Format::from_str`]: #method.from_str
 #[non_exhaustive]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Format {
+  #[cfg(feature = "bencode")]
+  Bencode,
   #[cfg(feature = "bincode")]
   Bincode,
+  #[cfg(feature = "bson")]
+  Bson,
   #[cfg(feature = "cbor")]
   Cbor,
+  #[cfg(feature = "csv")]
+  Csv,
+  #[cfg(feature = "flexbuffers")]
+  FlexBuffers,
+  #[cfg(feature = "ini")]
+  Ini,
   #[cfg(feature = "json")]
   Json,
+  #[cfg(feature = "json5")]
+  Json5,
   #[cfg(feature = "messagepack")]
   MessagePack,
+  #[cfg(feature = "pickle")]
+  Pickle,
+  #[cfg(feature = "plist")]
+  Plist,
+  #[cfg(feature = "postcard")]
+  Postcard,
+  #[cfg(feature = "properties")]
+  Properties,
   #[cfg(feature = "ron")]
   Ron,
+  #[cfg(feature = "sexpr")]
+  SExpr,
   #[cfg(feature = "toml")]
   Toml,
+  #[cfg(feature = "urlencoded")]
+  UrlEncoded,
   #[cfg(feature = "xml")]
-  Xml
+  Xml,
+  #[cfg(feature = "yaml")]
+  Yaml
 }
 
 impl Format {
+  /// Every format enabled by this crate's features, in alphabetical order.
+  pub const ALL: &'static [Format] = &[
+    #[cfg(feature = "bencode")]
+    Format::Bencode,
+    #[cfg(feature = "bincode")]
+    Format::Bincode,
+    #[cfg(feature = "bson")]
+    Format::Bson,
+    #[cfg(feature = "cbor")]
+    Format::Cbor,
+    #[cfg(feature = "csv")]
+    Format::Csv,
+    #[cfg(feature = "flexbuffers")]
+    Format::FlexBuffers,
+    #[cfg(feature = "ini")]
+    Format::Ini,
+    #[cfg(feature = "json")]
+    Format::Json,
+    #[cfg(feature = "json5")]
+    Format::Json5,
+    #[cfg(feature = "messagepack")]
+    Format::MessagePack,
+    #[cfg(feature = "pickle")]
+    Format::Pickle,
+    #[cfg(feature = "plist")]
+    Format::Plist,
+    #[cfg(feature = "postcard")]
+    Format::Postcard,
+    #[cfg(feature = "properties")]
+    Format::Properties,
+    #[cfg(feature = "ron")]
+    Format::Ron,
+    #[cfg(feature = "sexpr")]
+    Format::SExpr,
+    #[cfg(feature = "toml")]
+    Format::Toml,
+    #[cfg(feature = "urlencoded")]
+    Format::UrlEncoded,
+    #[cfg(feature = "xml")]
+    Format::Xml,
+    #[cfg(feature = "yaml")]
+    Format::Yaml
+  ];
+
+  /// Iterates over every format enabled by this crate's features.
+  #[inline]
+  pub fn iter() -> impl Iterator<Item = Format> {
+    Format::ALL.iter().copied()
+  }
+
+  /// Every format enabled by this crate's features.
+  /// This is the same as [`Format::ALL`], for use where a function is more convenient.
+  ///
+  /// [`Format::ALL`]: #associatedconstant.ALL
+  #[inline]
+  pub fn enabled() -> &'static [Format] {
+    Format::ALL
+  }
+
   /// Gets the name of the format currently being used.
   pub fn name(&self) -> &'static str {
     match self {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => "Bencode",
       #[cfg(feature = "bincode")]
       Format::Bincode => "Bincode",
+      #[cfg(feature = "bson")]
+      Format::Bson => "BSON",
       #[cfg(feature = "cbor")]
       Format::Cbor => "CBOR",
+      #[cfg(feature = "csv")]
+      Format::Csv => "CSV",
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => "FlexBuffers",
+      #[cfg(feature = "ini")]
+      Format::Ini => "INI",
       #[cfg(feature = "json")]
       Format::Json => "JSON",
+      #[cfg(feature = "json5")]
+      Format::Json5 => "JSON5",
       #[cfg(feature = "messagepack")]
       Format::MessagePack => "MessagePack",
+      #[cfg(feature = "pickle")]
+      Format::Pickle => "Pickle",
+      #[cfg(feature = "plist")]
+      Format::Plist => "Property list",
+      #[cfg(feature = "postcard")]
+      Format::Postcard => "Postcard",
+      #[cfg(feature = "properties")]
+      Format::Properties => "Java properties",
       #[cfg(feature = "ron")]
       Format::Ron => "RON",
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => "S-expression",
       #[cfg(feature = "toml")]
       Format::Toml => "TOML",
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => "URL-encoded",
       #[cfg(feature = "xml")]
       Format::Xml => "XML",
+      #[cfg(feature = "yaml")]
+      Format::Yaml => "YAML",
       #[allow(unreachable_patterns)]
       _ => panic!("unsupported")
     }
   }
 
-  /// Converts this format into a `dyn SerdeBytes`.
-  pub fn as_dyn_bytes(&self) -> &'static dyn SerdeBytes {
+  /// Converts this format into a `dyn DynSerdeBytes`.
+  pub fn as_dyn_bytes(&self) -> &'static dyn DynSerdeBytes {
     match self {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => &bencode::Bencode,
       #[cfg(feature = "bincode")]
-      Format::Bincode => &bincode::Bincode,
+      Format::Bincode => &BINCODE,
+      #[cfg(feature = "bson")]
+      Format::Bson => &bson::Bson,
       #[cfg(feature = "cbor")]
-      Format::Cbor => &cbor::Cbor,
+      Format::Cbor => &CBOR,
+      #[cfg(feature = "csv")]
+      Format::Csv => &CSV,
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => &flexbuffers::FlexBuffers,
+      #[cfg(feature = "ini")]
+      Format::Ini => &ini::Ini,
       #[cfg(feature = "json")]
-      Format::Json => &json::Json,
+      Format::Json => &JSON,
+      #[cfg(feature = "json5")]
+      Format::Json5 => &JSON5,
       #[cfg(feature = "messagepack")]
-      Format::MessagePack => &messagepack::MessagePack,
+      Format::MessagePack => &MESSAGEPACK,
+      #[cfg(feature = "pickle")]
+      Format::Pickle => &PICKLE,
+      #[cfg(feature = "plist")]
+      Format::Plist => &PLIST,
+      #[cfg(feature = "postcard")]
+      Format::Postcard => &POSTCARD,
+      #[cfg(feature = "properties")]
+      Format::Properties => &properties::Properties,
       #[cfg(feature = "ron")]
-      Format::Ron => &ron::Ron,
+      Format::Ron => &RON,
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => &sexpr::SExpr,
       #[cfg(feature = "toml")]
-      Format::Toml => &toml::Toml,
+      Format::Toml => &TOML,
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => &urlencoded::UrlEncoded,
       #[cfg(feature = "xml")]
-      Format::Xml => &xml::Xml,
+      Format::Xml => &XML,
+      #[cfg(feature = "yaml")]
+      Format::Yaml => &yaml::Yaml,
       #[allow(unreachable_patterns)]
       _ => panic!("unsupported")
     }
   }
 
-  /// Converts this format into a `dyn SerdeText`.
-  pub fn as_dyn_text(&self) -> &'static dyn SerdeText {
+  /// Converts this format into a `dyn DynSerdeText`.
+  pub fn as_dyn_text(&self) -> &'static dyn DynSerdeText {
     match self {
+      #[cfg(feature = "csv")]
+      Format::Csv => &CSV,
+      #[cfg(feature = "ini")]
+      Format::Ini => &ini::Ini,
       #[cfg(feature = "json")]
-      Format::Json => &json::Json,
+      Format::Json => &JSON,
+      #[cfg(feature = "json5")]
+      Format::Json5 => &JSON5,
+      #[cfg(feature = "plist")]
+      Format::Plist => &PLIST,
+      #[cfg(feature = "properties")]
+      Format::Properties => &properties::Properties,
       #[cfg(feature = "ron")]
-      Format::Ron => &ron::Ron,
+      Format::Ron => &RON,
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => &sexpr::SExpr,
       #[cfg(feature = "toml")]
-      Format::Toml => &toml::Toml,
+      Format::Toml => &TOML,
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => &urlencoded::UrlEncoded,
       #[cfg(feature = "xml")]
-      Format::Xml => &xml::Xml,
+      Format::Xml => &XML,
+      #[cfg(feature = "yaml")]
+      Format::Yaml => &yaml::Yaml,
       #[allow(unreachable_patterns)]
       _ => panic!("unsupported")
     }
   }
 
-  /// Converts this format into a `dyn SerdeStream`
-  pub fn as_dyn_stream(&self) -> &'static dyn SerdeStream {
+  /// Converts this format into a `dyn DynSerdeStream`
+  pub fn as_dyn_stream(&self) -> &'static dyn DynSerdeStream {
     match self {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => &bencode::Bencode,
       #[cfg(feature = "bincode")]
-      Format::Bincode => &bincode::Bincode,
+      Format::Bincode => &BINCODE,
+      #[cfg(feature = "bson")]
+      Format::Bson => &bson::Bson,
       #[cfg(feature = "cbor")]
-      Format::Cbor => &cbor::Cbor,
+      Format::Cbor => &CBOR,
+      #[cfg(feature = "csv")]
+      Format::Csv => &CSV,
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => &flexbuffers::FlexBuffers,
+      #[cfg(feature = "ini")]
+      Format::Ini => &ini::Ini,
       #[cfg(feature = "json")]
-      Format::Json => &json::Json,
+      Format::Json => &JSON,
+      #[cfg(feature = "json5")]
+      Format::Json5 => &JSON5,
       #[cfg(feature = "messagepack")]
-      Format::MessagePack => &messagepack::MessagePack,
+      Format::MessagePack => &MESSAGEPACK,
+      #[cfg(feature = "pickle")]
+      Format::Pickle => &PICKLE,
+      #[cfg(feature = "plist")]
+      Format::Plist => &PLIST,
+      #[cfg(feature = "postcard")]
+      Format::Postcard => &POSTCARD,
+      #[cfg(feature = "properties")]
+      Format::Properties => &properties::Properties,
       #[cfg(feature = "ron")]
-      Format::Ron => &ron::Ron,
+      Format::Ron => &RON,
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => &sexpr::SExpr,
       #[cfg(feature = "toml")]
-      Format::Toml => &toml::Toml,
+      Format::Toml => &TOML,
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => &urlencoded::UrlEncoded,
       #[cfg(feature = "xml")]
-      Format::Xml => &xml::Xml,
+      Format::Xml => &XML,
+      #[cfg(feature = "yaml")]
+      Format::Yaml => &yaml::Yaml,
       #[allow(unreachable_patterns)]
       _ => panic!("unsupported")
     }
   }
+
+  /// Picks a format based on a file extension, with or without the leading `.`, ignoring case.
+  pub fn from_extension(extension: &str) -> Result<Format, ParseFormatError> {
+    let extension = extension.trim_start_matches('.');
+    find(|format| format.extensions(), extension)
+      .ok_or_else(|| ParseFormatError::new(ParseFormatErrorKind::Extension, extension))
+  }
+
+  /// Picks a format based on the extension of the given path.
+  pub fn from_path(path: impl AsRef<Path>) -> Result<Format, ParseFormatError> {
+    let path = path.as_ref();
+    match path.extension().and_then(|extension| extension.to_str()) {
+      Some(extension) => Format::from_extension(extension),
+      None => Err(ParseFormatError::new(ParseFormatErrorKind::Extension, &path.to_string_lossy()))
+    }
+  }
+
+  /// Picks a format based on a MIME type, ignoring case and any parameters such as `charset`.
+  pub fn from_mime(mime_type: &str) -> Result<Format, ParseFormatError> {
+    let mime_type = mime_type.split(';').next().unwrap_or_default().trim();
+    find(|format| format.mime_types(), mime_type)
+      .ok_or_else(|| ParseFormatError::new(ParseFormatErrorKind::MimeType, mime_type))
+  }
+
+  /// The conventional file extension of this format.
+  pub fn extension(&self) -> &'static str {
+    match self {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => bencode::EXTENSION,
+      #[cfg(feature = "bincode")]
+      Format::Bincode => bincode::EXTENSION,
+      #[cfg(feature = "bson")]
+      Format::Bson => bson::EXTENSION,
+      #[cfg(feature = "cbor")]
+      Format::Cbor => cbor::EXTENSION,
+      #[cfg(feature = "csv")]
+      Format::Csv => csv::EXTENSION,
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => flexbuffers::EXTENSION,
+      #[cfg(feature = "ini")]
+      Format::Ini => ini::EXTENSION,
+      #[cfg(feature = "json")]
+      Format::Json => json::EXTENSION,
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::EXTENSION,
+      #[cfg(feature = "messagepack")]
+      Format::MessagePack => messagepack::EXTENSION,
+      #[cfg(feature = "pickle")]
+      Format::Pickle => pickle::EXTENSION,
+      #[cfg(feature = "plist")]
+      Format::Plist => plist::EXTENSION,
+      #[cfg(feature = "postcard")]
+      Format::Postcard => postcard::EXTENSION,
+      #[cfg(feature = "properties")]
+      Format::Properties => properties::EXTENSION,
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::EXTENSION,
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::EXTENSION,
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::EXTENSION,
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => urlencoded::EXTENSION,
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::EXTENSION,
+      #[cfg(feature = "yaml")]
+      Format::Yaml => yaml::EXTENSION,
+      #[allow(unreachable_patterns)]
+      _ => panic!("unsupported")
+    }
+  }
+
+  /// Every file extension of this format, starting with the one returned by `extension`.
+  pub fn extensions(&self) -> &'static [&'static str] {
+    match self {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => bencode::EXTENSIONS,
+      #[cfg(feature = "bincode")]
+      Format::Bincode => bincode::EXTENSIONS,
+      #[cfg(feature = "bson")]
+      Format::Bson => bson::EXTENSIONS,
+      #[cfg(feature = "cbor")]
+      Format::Cbor => cbor::EXTENSIONS,
+      #[cfg(feature = "csv")]
+      Format::Csv => csv::EXTENSIONS,
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => flexbuffers::EXTENSIONS,
+      #[cfg(feature = "ini")]
+      Format::Ini => ini::EXTENSIONS,
+      #[cfg(feature = "json")]
+      Format::Json => json::EXTENSIONS,
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::EXTENSIONS,
+      #[cfg(feature = "messagepack")]
+      Format::MessagePack => messagepack::EXTENSIONS,
+      #[cfg(feature = "pickle")]
+      Format::Pickle => pickle::EXTENSIONS,
+      #[cfg(feature = "plist")]
+      Format::Plist => plist::EXTENSIONS,
+      #[cfg(feature = "postcard")]
+      Format::Postcard => postcard::EXTENSIONS,
+      #[cfg(feature = "properties")]
+      Format::Properties => properties::EXTENSIONS,
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::EXTENSIONS,
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::EXTENSIONS,
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::EXTENSIONS,
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => urlencoded::EXTENSIONS,
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::EXTENSIONS,
+      #[cfg(feature = "yaml")]
+      Format::Yaml => yaml::EXTENSIONS,
+      #[allow(unreachable_patterns)]
+      _ => panic!("unsupported")
+    }
+  }
+
+  /// The MIME type of this format.
+  pub fn mime_type(&self) -> &'static str {
+    match self {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => bencode::MIME_TYPE,
+      #[cfg(feature = "bincode")]
+      Format::Bincode => bincode::MIME_TYPE,
+      #[cfg(feature = "bson")]
+      Format::Bson => bson::MIME_TYPE,
+      #[cfg(feature = "cbor")]
+      Format::Cbor => cbor::MIME_TYPE,
+      #[cfg(feature = "csv")]
+      Format::Csv => csv::MIME_TYPE,
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => flexbuffers::MIME_TYPE,
+      #[cfg(feature = "ini")]
+      Format::Ini => ini::MIME_TYPE,
+      #[cfg(feature = "json")]
+      Format::Json => json::MIME_TYPE,
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::MIME_TYPE,
+      #[cfg(feature = "messagepack")]
+      Format::MessagePack => messagepack::MIME_TYPE,
+      #[cfg(feature = "pickle")]
+      Format::Pickle => pickle::MIME_TYPE,
+      #[cfg(feature = "plist")]
+      Format::Plist => plist::MIME_TYPE,
+      #[cfg(feature = "postcard")]
+      Format::Postcard => postcard::MIME_TYPE,
+      #[cfg(feature = "properties")]
+      Format::Properties => properties::MIME_TYPE,
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::MIME_TYPE,
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::MIME_TYPE,
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::MIME_TYPE,
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => urlencoded::MIME_TYPE,
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::MIME_TYPE,
+      #[cfg(feature = "yaml")]
+      Format::Yaml => yaml::MIME_TYPE,
+      #[allow(unreachable_patterns)]
+      _ => panic!("unsupported")
+    }
+  }
+
+  /// Every MIME type of this format, starting with the one returned by `mime_type`.
+  pub fn mime_types(&self) -> &'static [&'static str] {
+    match self {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => bencode::MIME_TYPES,
+      #[cfg(feature = "bincode")]
+      Format::Bincode => bincode::MIME_TYPES,
+      #[cfg(feature = "bson")]
+      Format::Bson => bson::MIME_TYPES,
+      #[cfg(feature = "cbor")]
+      Format::Cbor => cbor::MIME_TYPES,
+      #[cfg(feature = "csv")]
+      Format::Csv => csv::MIME_TYPES,
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => flexbuffers::MIME_TYPES,
+      #[cfg(feature = "ini")]
+      Format::Ini => ini::MIME_TYPES,
+      #[cfg(feature = "json")]
+      Format::Json => json::MIME_TYPES,
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::MIME_TYPES,
+      #[cfg(feature = "messagepack")]
+      Format::MessagePack => messagepack::MIME_TYPES,
+      #[cfg(feature = "pickle")]
+      Format::Pickle => pickle::MIME_TYPES,
+      #[cfg(feature = "plist")]
+      Format::Plist => plist::MIME_TYPES,
+      #[cfg(feature = "postcard")]
+      Format::Postcard => postcard::MIME_TYPES,
+      #[cfg(feature = "properties")]
+      Format::Properties => properties::MIME_TYPES,
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::MIME_TYPES,
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::MIME_TYPES,
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::MIME_TYPES,
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => urlencoded::MIME_TYPES,
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::MIME_TYPES,
+      #[cfg(feature = "yaml")]
+      Format::Yaml => yaml::MIME_TYPES,
+      #[allow(unreachable_patterns)]
+      _ => panic!("unsupported")
+    }
+  }
+
+  /// Checks whether this format supports the given feature, without having to call
+  /// a function and check for a [`FormatError::Unsupported`].
+  ///
+  /// [`FormatError::Unsupported`]: ./enum.FormatError.html#variant.Unsupported
+  pub fn supports(&self, feature: Feature) -> bool {
+    match feature {
+      Feature::Text => self.is_text(),
+      Feature::Bytes | Feature::Stream | Feature::Extension => true,
+      Feature::Pretty => match self {
+        #[cfg(feature = "json")]
+        Format::Json => true,
+        #[cfg(feature = "json5")]
+        Format::Json5 => true,
+        #[cfg(feature = "ron")]
+        Format::Ron => true,
+        #[cfg(feature = "sexpr")]
+        Format::SExpr => true,
+        #[cfg(feature = "toml")]
+        Format::Toml => true,
+        #[cfg(feature = "xml")]
+        Format::Xml => true,
+        #[allow(unreachable_patterns)]
+        _ => false
+      }
+    }
+  }
+
+  /// Every feature this format supports.
+  pub fn features(&self) -> BTreeSet<Feature> {
+    Feature::ALL.iter().copied().filter(|&feature| self.supports(feature)).collect()
+  }
+
+  /// Whether this format is a text format, supporting [`Feature::Text`].
+  ///
+  /// `Format::Plist` always writes XML property lists, so it is a text format.
+  /// Binary ones are written by a [`Plist`] configured with `Encoding::Binary`, which can tell which it writes.
+  ///
+  /// [`Feature::Text`]: ./enum.Feature.html#variant.Text
+  /// [`Plist`]: ../formats/plist/struct.Plist.html
+  pub fn is_text(&self) -> bool {
+    match self {
+      #[cfg(feature = "csv")]
+      Format::Csv => true,
+      #[cfg(feature = "ini")]
+      Format::Ini => true,
+      #[cfg(feature = "json")]
+      Format::Json => true,
+      #[cfg(feature = "json5")]
+      Format::Json5 => true,
+      #[cfg(feature = "plist")]
+      Format::Plist => true,
+      #[cfg(feature = "properties")]
+      Format::Properties => true,
+      #[cfg(feature = "ron")]
+      Format::Ron => true,
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => true,
+      #[cfg(feature = "toml")]
+      Format::Toml => true,
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => true,
+      #[cfg(feature = "xml")]
+      Format::Xml => true,
+      #[cfg(feature = "yaml")]
+      Format::Yaml => true,
+      #[allow(unreachable_patterns)]
+      _ => false
+    }
+  }
+
+  /// Whether this format is a binary format, not supporting [`Feature::Text`].
+  ///
+  /// [`Feature::Text`]: ./enum.Feature.html#variant.Text
+  #[inline]
+  pub fn is_binary(&self) -> bool {
+    !self.is_text()
+  }
+
+  /// Whether data in this format can be parsed without knowing its type ahead of time.
+  /// Only self-describing formats can be deserialized with `deserialize_any`.
+  pub fn is_self_describing(&self) -> bool {
+    match self {
+      #[cfg(feature = "bincode")]
+      Format::Bincode => false,
+      #[cfg(feature = "postcard")]
+      Format::Postcard => false,
+      #[allow(unreachable_patterns)]
+      _ => true
+    }
+  }
+
+  /// The names this format can be parsed from, the first being the canonical one.
+  pub(crate) fn aliases(&self) -> &'static [&'static str] {
+    match self {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => &["bencode", "torrent"],
+      #[cfg(feature = "bincode")]
+      Format::Bincode => &["bincode"],
+      #[cfg(feature = "bson")]
+      Format::Bson => &["bson"],
+      #[cfg(feature = "cbor")]
+      Format::Cbor => &["cbor"],
+      #[cfg(feature = "csv")]
+      Format::Csv => &["csv"],
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => &["flexbuffers", "flexbuffer"],
+      #[cfg(feature = "ini")]
+      Format::Ini => &["ini"],
+      #[cfg(feature = "json")]
+      Format::Json => &["json"],
+      #[cfg(feature = "json5")]
+      Format::Json5 => &["json5"],
+      #[cfg(feature = "messagepack")]
+      Format::MessagePack => &["messagepack", "msgpack", "mpk", "rmp"],
+      #[cfg(feature = "pickle")]
+      Format::Pickle => &["pickle", "pkl"],
+      #[cfg(feature = "plist")]
+      Format::Plist => &["plist"],
+      #[cfg(feature = "postcard")]
+      Format::Postcard => &["postcard"],
+      #[cfg(feature = "properties")]
+      Format::Properties => &["properties", "java-properties"],
+      #[cfg(feature = "ron")]
+      Format::Ron => &["ron"],
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => &["sexpr", "sexp", "s-expression"],
+      #[cfg(feature = "toml")]
+      Format::Toml => &["toml"],
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => &["urlencoded", "x-www-form-urlencoded"],
+      #[cfg(feature = "xml")]
+      Format::Xml => &["xml"],
+      #[cfg(feature = "yaml")]
+      Format::Yaml => &["yaml", "yml"],
+      #[allow(unreachable_patterns)]
+      _ => panic!("unsupported")
+    }
+  }
+}
+
+fn find(keys: impl Fn(&Format) -> &'static [&'static str], input: &str) -> Option<Format> {
+  Format::iter().find(|format| keys(format).iter().any(|key| key.eq_ignore_ascii_case(input)))
 }
 
 impl SerdeText for Format {
@@ -156,6 +762,12 @@ impl SerdeText for Format {
   where T: Deserialize<'d> {
     from_str(*self, data).map_err(map_err)
   }
+
+  #[inline]
+  fn from_str_seed<'d, S>(&self, seed: S, data: &'d str) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_str_seed(*self, seed, data).map_err(map_err)
+  }
 }
 
 impl SerdeBytes for Format {
@@ -176,6 +788,12 @@ impl SerdeBytes for Format {
   where T: Deserialize<'d> {
     from_slice(*self, data).map_err(map_err)
   }
+
+  #[inline]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, crate::Error>
+  where S: DeserializeSeed<'d> {
+    from_slice_seed(*self, seed, data).map_err(map_err)
+  }
 }
 
 impl SerdeStream for Format {
@@ -196,6 +814,12 @@ impl SerdeStream for Format {
   where R: Read, T: DeserializeOwned {
     from_reader(*self, reader).map_err(map_err)
   }
+
+  #[inline]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, crate::Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    from_reader_seed(*self, seed, reader).map_err(map_err)
+  }
 }
 
 impl Display for Format {
@@ -205,62 +829,394 @@ impl Display for Format {
   }
 }
 
+impl FromStr for Format {
+  type Err = ParseFormatError;
+
+  /// Parses a format from its name or one of its aliases, ignoring case.
+  fn from_str(name: &str) -> Result<Format, ParseFormatError> {
+    let name = name.trim();
+    find(|format| format.aliases(), name)
+      .ok_or_else(|| ParseFormatError::new(ParseFormatErrorKind::Name, name))
+  }
+}
+
+impl Serialize for Format {
+  #[inline]
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where S: Serializer {
+    serializer.serialize_str(self.aliases()[0])
+  }
+}
+
+impl<'de> Deserialize<'de> for Format {
+  #[inline]
+  fn deserialize<D>(deserializer: D) -> Result<Format, D::Error>
+  where D: Deserializer<'de> {
+    deserializer.deserialize_str(FormatVisitor)
+  }
+}
+
+struct FormatVisitor;
+
+impl<'de> Visitor<'de> for FormatVisitor {
+  type Value = Format;
+
+  #[inline]
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "a format name")
+  }
+
+  #[inline]
+  fn visit_str<E>(self, value: &str) -> Result<Format, E>
+  where E: de::Error {
+    value.parse().map_err(E::custom)
+  }
+}
+
+#[cfg(feature = "bencode")]
+impl From<bencode::Bencode> for Format {
+  #[inline]
+  fn from(_: bencode::Bencode) -> Format {
+    Format::Bencode
+  }
+}
+
+#[cfg(feature = "bson")]
+impl From<bson::Bson> for Format {
+  #[inline]
+  fn from(_: bson::Bson) -> Format {
+    Format::Bson
+  }
+}
+
+#[cfg(feature = "flexbuffers")]
+impl From<flexbuffers::FlexBuffers> for Format {
+  #[inline]
+  fn from(_: flexbuffers::FlexBuffers) -> Format {
+    Format::FlexBuffers
+  }
+}
+
+#[cfg(feature = "ini")]
+impl From<ini::Ini> for Format {
+  #[inline]
+  fn from(_: ini::Ini) -> Format {
+    Format::Ini
+  }
+}
+
+#[cfg(feature = "properties")]
+impl From<properties::Properties> for Format {
+  #[inline]
+  fn from(_: properties::Properties) -> Format {
+    Format::Properties
+  }
+}
+
+#[cfg(feature = "sexpr")]
+impl From<sexpr::SExpr> for Format {
+  #[inline]
+  fn from(_: sexpr::SExpr) -> Format {
+    Format::SExpr
+  }
+}
+
+#[cfg(feature = "urlencoded")]
+impl From<urlencoded::UrlEncoded> for Format {
+  #[inline]
+  fn from(_: urlencoded::UrlEncoded) -> Format {
+    Format::UrlEncoded
+  }
+}
+
+#[cfg(feature = "yaml")]
+impl From<yaml::Yaml> for Format {
+  #[inline]
+  fn from(_: yaml::Yaml) -> Format {
+    Format::Yaml
+  }
+}
+
+/// A [`Format`] together with the options of its format struct, accepted by every function in this module.
+///
+/// A `Format` converts into `Configured::Default`, which uses the default options of that format,
+/// and each format struct with options converts into its own variant, keeping those options.
+///
+/// [`Format`]: ./enum.Format.html
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub enum Configured {
+  /// A format with its default options.
+  Default(Format),
+  #[cfg(feature = "bincode")]
+  Bincode(bincode::Bincode),
+  #[cfg(feature = "cbor")]
+  Cbor(cbor::Cbor),
+  #[cfg(feature = "csv")]
+  Csv(csv::Csv),
+  #[cfg(feature = "json")]
+  Json(json::Json),
+  #[cfg(feature = "json5")]
+  Json5(json5::Json5),
+  #[cfg(feature = "messagepack")]
+  MessagePack(messagepack::MessagePack),
+  #[cfg(feature = "pickle")]
+  Pickle(pickle::Pickle),
+  #[cfg(feature = "plist")]
+  Plist(plist::Plist),
+  #[cfg(feature = "postcard")]
+  Postcard(postcard::Postcard),
+  #[cfg(feature = "ron")]
+  Ron(ron::Ron),
+  #[cfg(feature = "toml")]
+  Toml(toml::Toml),
+  #[cfg(feature = "xml")]
+  Xml(xml::Xml)
+}
+
+impl Configured {
+  /// The format these options are for.
+  pub fn format(&self) -> Format {
+    match self {
+      Configured::Default(format) => *format,
+      #[cfg(feature = "bincode")]
+      Configured::Bincode(_) => Format::Bincode,
+      #[cfg(feature = "cbor")]
+      Configured::Cbor(_) => Format::Cbor,
+      #[cfg(feature = "csv")]
+      Configured::Csv(_) => Format::Csv,
+      #[cfg(feature = "json")]
+      Configured::Json(_) => Format::Json,
+      #[cfg(feature = "json5")]
+      Configured::Json5(_) => Format::Json5,
+      #[cfg(feature = "messagepack")]
+      Configured::MessagePack(_) => Format::MessagePack,
+      #[cfg(feature = "pickle")]
+      Configured::Pickle(_) => Format::Pickle,
+      #[cfg(feature = "plist")]
+      Configured::Plist(_) => Format::Plist,
+      #[cfg(feature = "postcard")]
+      Configured::Postcard(_) => Format::Postcard,
+      #[cfg(feature = "ron")]
+      Configured::Ron(_) => Format::Ron,
+      #[cfg(feature = "toml")]
+      Configured::Toml(_) => Format::Toml,
+      #[cfg(feature = "xml")]
+      Configured::Xml(_) => Format::Xml
+    }
+  }
+
+  /// Converts this format into a `dyn DynSerdeBytes`.
+  pub fn as_dyn_bytes(&self) -> &dyn DynSerdeBytes {
+    match self {
+      Configured::Default(format) => format.as_dyn_bytes(),
+      #[cfg(feature = "bincode")]
+      Configured::Bincode(bincode) => bincode,
+      #[cfg(feature = "cbor")]
+      Configured::Cbor(cbor) => cbor,
+      #[cfg(feature = "csv")]
+      Configured::Csv(csv) => csv,
+      #[cfg(feature = "json")]
+      Configured::Json(json) => json,
+      #[cfg(feature = "json5")]
+      Configured::Json5(json5) => json5,
+      #[cfg(feature = "messagepack")]
+      Configured::MessagePack(messagepack) => messagepack,
+      #[cfg(feature = "pickle")]
+      Configured::Pickle(pickle) => pickle,
+      #[cfg(feature = "plist")]
+      Configured::Plist(plist) => plist,
+      #[cfg(feature = "postcard")]
+      Configured::Postcard(postcard) => postcard,
+      #[cfg(feature = "ron")]
+      Configured::Ron(ron) => ron,
+      #[cfg(feature = "toml")]
+      Configured::Toml(toml) => toml,
+      #[cfg(feature = "xml")]
+      Configured::Xml(xml) => xml
+    }
+  }
+
+  /// Converts this format into a `dyn DynSerdeText`.
+  pub fn as_dyn_text(&self) -> &dyn DynSerdeText {
+    match self {
+      Configured::Default(format) => format.as_dyn_text(),
+      #[cfg(feature = "csv")]
+      Configured::Csv(csv) => csv,
+      #[cfg(feature = "json")]
+      Configured::Json(json) => json,
+      #[cfg(feature = "json5")]
+      Configured::Json5(json5) => json5,
+      #[cfg(feature = "plist")]
+      Configured::Plist(plist) => plist,
+      #[cfg(feature = "ron")]
+      Configured::Ron(ron) => ron,
+      #[cfg(feature = "toml")]
+      Configured::Toml(toml) => toml,
+      #[cfg(feature = "xml")]
+      Configured::Xml(xml) => xml,
+      #[allow(unreachable_patterns)]
+      _ => panic!("unsupported")
+    }
+  }
+
+  /// Converts this format into a `dyn DynSerdeStream`
+  pub fn as_dyn_stream(&self) -> &dyn DynSerdeStream {
+    match self {
+      Configured::Default(format) => format.as_dyn_stream(),
+      #[cfg(feature = "bincode")]
+      Configured::Bincode(bincode) => bincode,
+      #[cfg(feature = "cbor")]
+      Configured::Cbor(cbor) => cbor,
+      #[cfg(feature = "csv")]
+      Configured::Csv(csv) => csv,
+      #[cfg(feature = "json")]
+      Configured::Json(json) => json,
+      #[cfg(feature = "json5")]
+      Configured::Json5(json5) => json5,
+      #[cfg(feature = "messagepack")]
+      Configured::MessagePack(messagepack) => messagepack,
+      #[cfg(feature = "pickle")]
+      Configured::Pickle(pickle) => pickle,
+      #[cfg(feature = "plist")]
+      Configured::Plist(plist) => plist,
+      #[cfg(feature = "postcard")]
+      Configured::Postcard(postcard) => postcard,
+      #[cfg(feature = "ron")]
+      Configured::Ron(ron) => ron,
+      #[cfg(feature = "toml")]
+      Configured::Toml(toml) => toml,
+      #[cfg(feature = "xml")]
+      Configured::Xml(xml) => xml
+    }
+  }
+
+  fn text(&self, feature: Feature) -> Result<&dyn DynSerdeText, FormatError> {
+    match self.format().supports(feature) {
+      true => Ok(self.as_dyn_text()),
+      false => Err(unsupported(self.format(), feature))
+    }
+  }
+
+  fn bytes(&self, feature: Feature) -> Result<&dyn DynSerdeBytes, FormatError> {
+    match self.format().supports(feature) {
+      true => Ok(self.as_dyn_bytes()),
+      false => Err(unsupported(self.format(), feature))
+    }
+  }
+
+  fn stream(&self, feature: Feature) -> Result<&dyn DynSerdeStream, FormatError> {
+    match self.format().supports(feature) {
+      true => Ok(self.as_dyn_stream()),
+      false => Err(unsupported(self.format(), feature))
+    }
+  }
+}
+
+impl From<Format> for Configured {
+  #[inline]
+  fn from(format: Format) -> Configured {
+    Configured::Default(format)
+  }
+}
+
 #[cfg(feature = "bincode")]
-impl From<bincode::Bincode> for Format {
+impl From<bincode::Bincode> for Configured {
   #[inline]
-  fn from(_: bincode::Bincode) -> Format {
-    Format::Bincode
+  fn from(bincode: bincode::Bincode) -> Configured {
+    Configured::Bincode(bincode)
   }
 }
 
 #[cfg(feature = "cbor")]
-impl From<cbor::Cbor> for Format {
+impl From<cbor::Cbor> for Configured {
   #[inline]
-  fn from(_: cbor::Cbor) -> Format {
-    Format::Cbor
+  fn from(cbor: cbor::Cbor) -> Configured {
+    Configured::Cbor(cbor)
+  }
+}
+
+#[cfg(feature = "csv")]
+impl From<csv::Csv> for Configured {
+  #[inline]
+  fn from(csv: csv::Csv) -> Configured {
+    Configured::Csv(csv)
   }
 }
 
 #[cfg(feature = "json")]
-impl From<json::Json> for Format {
+impl From<json::Json> for Configured {
+  #[inline]
+  fn from(json: json::Json) -> Configured {
+    Configured::Json(json)
+  }
+}
+
+#[cfg(feature = "json5")]
+impl From<json5::Json5> for Configured {
   #[inline]
-  fn from(_: json::Json) -> Format {
-    Format::Json
+  fn from(json5: json5::Json5) -> Configured {
+    Configured::Json5(json5)
   }
 }
 
 #[cfg(feature = "messagepack")]
-impl From<messagepack::MessagePack> for Format {
+impl From<messagepack::MessagePack> for Configured {
+  #[inline]
+  fn from(messagepack: messagepack::MessagePack) -> Configured {
+    Configured::MessagePack(messagepack)
+  }
+}
+
+#[cfg(feature = "pickle")]
+impl From<pickle::Pickle> for Configured {
+  #[inline]
+  fn from(pickle: pickle::Pickle) -> Configured {
+    Configured::Pickle(pickle)
+  }
+}
+
+#[cfg(feature = "plist")]
+impl From<plist::Plist> for Configured {
+  #[inline]
+  fn from(plist: plist::Plist) -> Configured {
+    Configured::Plist(plist)
+  }
+}
+
+#[cfg(feature = "postcard")]
+impl From<postcard::Postcard> for Configured {
   #[inline]
-  fn from(_: messagepack::MessagePack) -> Format {
-    Format::MessagePack
+  fn from(postcard: postcard::Postcard) -> Configured {
+    Configured::Postcard(postcard)
   }
 }
 
 #[cfg(feature = "ron")]
-impl From<ron::Ron> for Format {
+impl From<ron::Ron> for Configured {
   #[inline]
-  fn from(_: ron::Ron) -> Format {
-    Format::Ron
+  fn from(ron: ron::Ron) -> Configured {
+    Configured::Ron(ron)
   }
 }
 
 #[cfg(feature = "toml")]
-impl From<toml::Toml> for Format {
+impl From<toml::Toml> for Configured {
   #[inline]
-  fn from(_: toml::Toml) -> Format {
-    Format::Toml
+  fn from(toml: toml::Toml) -> Configured {
+    Configured::Toml(toml)
   }
 }
 
 #[cfg(feature = "xml")]
-impl From<xml::Xml> for Format {
+impl From<xml::Xml> for Configured {
   #[inline]
-  fn from(_: xml::Xml) -> Format {
-    Format::Xml
+  fn from(xml: xml::Xml) -> Configured {
+    Configured::Xml(xml)
   }
 }
 
+
 /// An error caused by one of the functions in [`multi`].
 ///
 /// [`multi`]: ./index.html
@@ -289,7 +1245,10 @@ impl Display for FormatError {
 
 impl std::error::Error for FormatError {}
 
-#[derive(Debug, Copy, Clone)]
+/// An operation that a [`Format`] may or may not support.
+///
+/// [`Format`]: ./enum.Format.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Feature {
   Text,
   Bytes,
@@ -298,6 +1257,11 @@ pub enum Feature {
   Extension
 }
 
+impl Feature {
+  /// Every feature a format can support.
+  pub const ALL: &'static [Feature] = &[Feature::Text, Feature::Bytes, Feature::Stream, Feature::Pretty, Feature::Extension];
+}
+
 impl Display for Feature {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     match self {
@@ -305,7 +1269,7 @@ impl Display for Feature {
       Feature::Bytes => write!(f, "binary serialization/deserialization"),
       Feature::Stream => write!(f, "stream serialization/deserialization"),
       Feature::Pretty => write!(f, "pretty-print serialization"),
-      Feature::Extension => writeln!(f, "file extensions")
+      Feature::Extension => write!(f, "file extensions")
     }
   }
 }
@@ -333,6 +1297,67 @@ impl Display for Unsupported {
 
 impl std::error::Error for Unsupported {}
 
+/// An error caused by failing to pick a [`Format`] from a name, file extension or MIME type.
+///
+/// [`Format`]: ./enum.Format.html
+#[derive(Debug, Clone)]
+pub struct ParseFormatError {
+  /// The string that did not match any enabled format.
+  pub input: String,
+  /// What the string was expected to be.
+  pub kind: ParseFormatErrorKind
+}
+
+impl ParseFormatError {
+  #[inline]
+  fn new(kind: ParseFormatErrorKind, input: &str) -> Self {
+    ParseFormatError { input: input.to_owned(), kind }
+  }
+}
+
+impl Display for ParseFormatError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "unknown {} `{}`", self.kind, self.input)?;
+    let alternatives = Format::iter().filter_map(|format| match self.kind {
+      ParseFormatErrorKind::Name => format.aliases().first(),
+      ParseFormatErrorKind::Extension => format.extensions().first(),
+      ParseFormatErrorKind::MimeType => format.mime_types().first()
+    });
+
+    for (i, alternative) in alternatives.enumerate() {
+      f.write_str(if i == 0 { ", expected one of: " } else { ", " })?;
+      f.write_str(alternative)?;
+    }
+
+    Ok(())
+  }
+}
+
+impl std::error::Error for ParseFormatError {}
+
+/// What kind of string a [`ParseFormatError`] failed to match.
+///
+/// [`ParseFormatError`]: ./struct.ParseFormatError.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseFormatErrorKind {
+  /// A format name or alias, such as `json` or `MessagePack`.
+  Name,
+  /// A file extension, such as `yml`.
+  Extension,
+  /// A MIME type, such as `application/json`.
+  MimeType
+}
+
+impl Display for ParseFormatErrorKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseFormatErrorKind::Name => write!(f, "format name"),
+      ParseFormatErrorKind::Extension => write!(f, "file extension"),
+      ParseFormatErrorKind::MimeType => write!(f, "MIME type")
+    }
+  }
+}
+
 fn map_err(format_error: FormatError) -> crate::Error {
   match format_error {
     FormatError::Error(error) => error,
@@ -345,178 +1370,540 @@ fn unsupported(format: Format, feature: Feature) -> FormatError {
   FormatError::Unsupported(Unsupported { format, feature })
 }
 
-pub fn to_string_pretty<T>(format: Format, value: &T) -> Result<String, FormatError>
+pub fn to_string_pretty<T>(format: impl Into<Configured>, value: &T) -> Result<String, FormatError>
 where T: Serialize {
-  match format {
-    #[cfg(feature = "json")]
-    Format::Json => json::to_string_pretty(value).map_err(FormatError::Error),
-    #[cfg(feature = "ron")]
-    Format::Ron => ron::to_string_pretty(value).map_err(FormatError::Error),
-    #[cfg(feature = "toml")]
-    Format::Toml => toml::to_string_pretty(value).map_err(FormatError::Error),
+  match format.into() {
+    Configured::Default(format) => match format {
+      #[cfg(feature = "json")]
+      Format::Json => json::to_string_pretty(value).map_err(FormatError::Error),
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::to_string_pretty(value).map_err(FormatError::Error),
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::to_string_pretty(value).map_err(FormatError::Error),
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::to_string_pretty(value).map_err(FormatError::Error),
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::to_string_pretty(value).map_err(FormatError::Error),
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::to_string_pretty(value).map_err(FormatError::Error),
+      #[allow(unreachable_patterns)]
+      _ => Err(unsupported(format, Feature::Pretty))
+    },
     #[allow(unreachable_patterns)]
-    _ => Err(unsupported(format, Feature::Pretty))
+    configured => configured.text(Feature::Pretty)?.to_string_pretty(value).map_err(FormatError::Error)
   }
 }
 
-pub fn to_string<T>(format: Format, value: &T) -> Result<String, FormatError>
+pub fn to_string<T>(format: impl Into<Configured>, value: &T) -> Result<String, FormatError>
 where T: Serialize {
-  match format {
-    #[cfg(feature = "json")]
-    Format::Json => json::to_string(value).map_err(FormatError::Error),
-    #[cfg(feature = "ron")]
-    Format::Ron => ron::to_string(value).map_err(FormatError::Error),
-    #[cfg(feature = "toml")]
-    Format::Toml => toml::to_string(value).map_err(FormatError::Error),
-    #[cfg(feature = "xml")]
-    Format::Xml => xml::to_string(value).map_err(FormatError::Error),
+  match format.into() {
+    Configured::Default(format) => match format {
+      #[cfg(feature = "csv")]
+      Format::Csv => csv::to_string(value).map_err(FormatError::Error),
+      #[cfg(feature = "ini")]
+      Format::Ini => ini::to_string(value).map_err(FormatError::Error),
+      #[cfg(feature = "json")]
+      Format::Json => json::to_string(value).map_err(FormatError::Error),
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::to_string(value).map_err(FormatError::Error),
+      #[cfg(feature = "plist")]
+      Format::Plist => plist::to_string(value).map_err(FormatError::Error),
+      #[cfg(feature = "properties")]
+      Format::Properties => properties::to_string(value).map_err(FormatError::Error),
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::to_string(value).map_err(FormatError::Error),
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::to_string(value).map_err(FormatError::Error),
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::to_string(value).map_err(FormatError::Error),
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => urlencoded::to_string(value).map_err(FormatError::Error),
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::to_string(value).map_err(FormatError::Error),
+      #[cfg(feature = "yaml")]
+      Format::Yaml => yaml::to_string(value).map_err(FormatError::Error),
+      #[allow(unreachable_patterns)]
+      _ => Err(unsupported(format, Feature::Text))
+    },
     #[allow(unreachable_patterns)]
-    _ => Err(unsupported(format, Feature::Text))
+    configured => configured.text(Feature::Text)?.to_string(value).map_err(FormatError::Error)
   }
 }
 
-pub fn from_str<'d, T>(format: Format, data: &'d str) -> Result<T, FormatError>
+pub fn from_str<'d, T>(format: impl Into<Configured>, data: &'d str) -> Result<T, FormatError>
 where T: Deserialize<'d> {
-  match format {
-    #[cfg(feature = "json")]
-    Format::Json => json::from_str(data).map_err(FormatError::Error),
-    #[cfg(feature = "ron")]
-    Format::Ron => ron::from_str(data).map_err(FormatError::Error),
-    #[cfg(feature = "toml")]
-    Format::Toml => toml::from_str(data).map_err(FormatError::Error),
-    #[cfg(feature = "xml")]
-    Format::Xml => xml::from_str(data).map_err(FormatError::Error),
+  match format.into() {
+    Configured::Default(format) => match format {
+      #[cfg(feature = "csv")]
+      Format::Csv => csv::from_str(data).map_err(FormatError::Error),
+      #[cfg(feature = "ini")]
+      Format::Ini => ini::from_str(data).map_err(FormatError::Error),
+      #[cfg(feature = "json")]
+      Format::Json => json::from_str(data).map_err(FormatError::Error),
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::from_str(data).map_err(FormatError::Error),
+      #[cfg(feature = "plist")]
+      Format::Plist => plist::from_str(data).map_err(FormatError::Error),
+      #[cfg(feature = "properties")]
+      Format::Properties => properties::from_str(data).map_err(FormatError::Error),
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::from_str(data).map_err(FormatError::Error),
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::from_str(data).map_err(FormatError::Error),
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::from_str(data).map_err(FormatError::Error),
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => urlencoded::from_str(data).map_err(FormatError::Error),
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::from_str(data).map_err(FormatError::Error),
+      #[cfg(feature = "yaml")]
+      Format::Yaml => yaml::from_str(data).map_err(FormatError::Error),
+      #[allow(unreachable_patterns)]
+      _ => Err(unsupported(format, Feature::Text))
+    },
+    #[allow(unreachable_patterns)]
+    configured => configured.text(Feature::Text)?.from_str(data).map_err(FormatError::Error)
+  }
+}
+
+pub fn from_str_seed<'d, S>(format: impl Into<Configured>, seed: S, data: &'d str) -> Result<S::Value, FormatError>
+where S: DeserializeSeed<'d> {
+  match format.into() {
+    Configured::Default(format) => match format {
+      #[cfg(feature = "csv")]
+      Format::Csv => csv::from_str_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "ini")]
+      Format::Ini => ini::from_str_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "json")]
+      Format::Json => json::from_str_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::from_str_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "plist")]
+      Format::Plist => plist::from_str_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "properties")]
+      Format::Properties => properties::from_str_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::from_str_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::from_str_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::from_str_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => urlencoded::from_str_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::from_str_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "yaml")]
+      Format::Yaml => yaml::from_str_seed(seed, data).map_err(FormatError::Error),
+      #[allow(unreachable_patterns)]
+      _ => Err(unsupported(format, Feature::Text))
+    },
     #[allow(unreachable_patterns)]
-    _ => Err(unsupported(format, Feature::Text))
+    configured => configured.text(Feature::Text)?.from_str_seed(seed, data).map_err(FormatError::Error)
   }
 }
 
-pub fn to_vec_pretty<T>(format: Format, value: &T) -> Result<Vec<u8>, FormatError>
+pub fn to_vec_pretty<T>(format: impl Into<Configured>, value: &T) -> Result<Vec<u8>, FormatError>
 where T: Serialize {
-  match format {
-    #[cfg(feature = "json")]
-    Format::Json => json::to_vec_pretty(value).map_err(FormatError::Error),
-    #[cfg(feature = "ron")]
-    Format::Ron => ron::to_vec_pretty(value).map_err(FormatError::Error),
-    #[cfg(feature = "toml")]
-    Format::Toml => toml::to_vec_pretty(value).map_err(FormatError::Error),
+  match format.into() {
+    Configured::Default(format) => match format {
+      #[cfg(feature = "json")]
+      Format::Json => json::to_vec_pretty(value).map_err(FormatError::Error),
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::to_vec_pretty(value).map_err(FormatError::Error),
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::to_vec_pretty(value).map_err(FormatError::Error),
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::to_vec_pretty(value).map_err(FormatError::Error),
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::to_vec_pretty(value).map_err(FormatError::Error),
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::to_vec_pretty(value).map_err(FormatError::Error),
+      #[allow(unreachable_patterns)]
+      _ => Err(unsupported(format, Feature::Pretty))
+    },
     #[allow(unreachable_patterns)]
-    _ => Err(unsupported(format, Feature::Pretty))
+    configured => configured.bytes(Feature::Pretty)?.to_vec_pretty(value).map_err(FormatError::Error)
   }
 }
 
-pub fn to_vec<T>(format: Format, value: &T) -> Result<Vec<u8>, FormatError>
+pub fn to_vec<T>(format: impl Into<Configured>, value: &T) -> Result<Vec<u8>, FormatError>
 where T: Serialize {
-  match format {
-    #[cfg(feature = "bincode")]
-    Format::Bincode => bincode::to_vec(value).map_err(FormatError::Error),
-    #[cfg(feature = "cbor")]
-    Format::Cbor => cbor::to_vec(value).map_err(FormatError::Error),
-    #[cfg(feature = "json")]
-    Format::Json => json::to_vec(value).map_err(FormatError::Error),
-    #[cfg(feature = "messagepack")]
-    Format::MessagePack => messagepack::to_vec(value).map_err(FormatError::Error),
-    #[cfg(feature = "ron")]
-    Format::Ron => ron::to_vec(value).map_err(FormatError::Error),
-    #[cfg(feature = "toml")]
-    Format::Toml => toml::to_vec(value).map_err(FormatError::Error),
-    #[cfg(feature = "xml")]
-    Format::Xml => xml::to_vec(value).map_err(FormatError::Error),
+  match format.into() {
+    Configured::Default(format) => match format {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => bencode::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "bincode")]
+      Format::Bincode => bincode::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "bson")]
+      Format::Bson => bson::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "cbor")]
+      Format::Cbor => cbor::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "csv")]
+      Format::Csv => csv::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => flexbuffers::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "ini")]
+      Format::Ini => ini::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "json")]
+      Format::Json => json::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "messagepack")]
+      Format::MessagePack => messagepack::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "pickle")]
+      Format::Pickle => pickle::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "plist")]
+      Format::Plist => plist::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "postcard")]
+      Format::Postcard => postcard::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "properties")]
+      Format::Properties => properties::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => urlencoded::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::to_vec(value).map_err(FormatError::Error),
+      #[cfg(feature = "yaml")]
+      Format::Yaml => yaml::to_vec(value).map_err(FormatError::Error),
+      #[allow(unreachable_patterns)]
+      _ => Err(unsupported(format, Feature::Bytes))
+    },
     #[allow(unreachable_patterns)]
-    _ => Err(unsupported(format, Feature::Bytes))
+    configured => configured.bytes(Feature::Bytes)?.to_vec(value).map_err(FormatError::Error)
   }
 }
 
-pub fn from_slice<'d, T>(format: Format, data: &'d [u8]) -> Result<T, FormatError>
+pub fn from_slice<'d, T>(format: impl Into<Configured>, data: &'d [u8]) -> Result<T, FormatError>
 where T: Deserialize<'d> {
-  match format {
-    #[cfg(feature = "bincode")]
-    Format::Bincode => bincode::from_slice(data).map_err(FormatError::Error),
-    #[cfg(feature = "cbor")]
-    Format::Cbor => cbor::from_slice(data).map_err(FormatError::Error),
-    #[cfg(feature = "json")]
-    Format::Json => json::from_slice(data).map_err(FormatError::Error),
-    #[cfg(feature = "messagepack")]
-    Format::MessagePack => messagepack::from_slice(data).map_err(FormatError::Error),
-    #[cfg(feature = "ron")]
-    Format::Ron => ron::from_slice(data).map_err(FormatError::Error),
-    #[cfg(feature = "toml")]
-    Format::Toml => toml::from_slice(data).map_err(FormatError::Error),
-    #[cfg(feature = "xml")]
-    Format::Xml => xml::from_slice(data).map_err(FormatError::Error),
+  match format.into() {
+    Configured::Default(format) => match format {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => bencode::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "bincode")]
+      Format::Bincode => bincode::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "bson")]
+      Format::Bson => bson::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "cbor")]
+      Format::Cbor => cbor::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "csv")]
+      Format::Csv => csv::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => flexbuffers::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "ini")]
+      Format::Ini => ini::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "json")]
+      Format::Json => json::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "messagepack")]
+      Format::MessagePack => messagepack::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "pickle")]
+      Format::Pickle => pickle::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "plist")]
+      Format::Plist => plist::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "postcard")]
+      Format::Postcard => postcard::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "properties")]
+      Format::Properties => properties::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => urlencoded::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::from_slice(data).map_err(FormatError::Error),
+      #[cfg(feature = "yaml")]
+      Format::Yaml => yaml::from_slice(data).map_err(FormatError::Error),
+      #[allow(unreachable_patterns)]
+      _ => Err(unsupported(format, Feature::Bytes))
+    },
+    #[allow(unreachable_patterns)]
+    configured => configured.bytes(Feature::Bytes)?.from_slice(data).map_err(FormatError::Error)
+  }
+}
+
+pub fn from_slice_seed<'d, S>(format: impl Into<Configured>, seed: S, data: &'d [u8]) -> Result<S::Value, FormatError>
+where S: DeserializeSeed<'d> {
+  match format.into() {
+    Configured::Default(format) => match format {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => bencode::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "bincode")]
+      Format::Bincode => bincode::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "bson")]
+      Format::Bson => bson::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "cbor")]
+      Format::Cbor => cbor::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "csv")]
+      Format::Csv => csv::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => flexbuffers::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "ini")]
+      Format::Ini => ini::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "json")]
+      Format::Json => json::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "messagepack")]
+      Format::MessagePack => messagepack::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "pickle")]
+      Format::Pickle => pickle::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "plist")]
+      Format::Plist => plist::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "postcard")]
+      Format::Postcard => postcard::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "properties")]
+      Format::Properties => properties::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => urlencoded::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[cfg(feature = "yaml")]
+      Format::Yaml => yaml::from_slice_seed(seed, data).map_err(FormatError::Error),
+      #[allow(unreachable_patterns)]
+      _ => Err(unsupported(format, Feature::Bytes))
+    },
     #[allow(unreachable_patterns)]
-    _ => Err(unsupported(format, Feature::Stream))
+    configured => configured.bytes(Feature::Bytes)?.from_slice_seed(seed, data).map_err(FormatError::Error)
   }
 }
 
-pub fn to_writer_pretty<W, T>(format: Format, writer: W, value: &T) -> Result<(), FormatError>
+pub fn to_writer_pretty<W, T>(format: impl Into<Configured>, writer: W, value: &T) -> Result<(), FormatError>
 where W: Write, T: Serialize {
-  match format {
-    #[cfg(feature = "json")]
-    Format::Json => json::to_writer_pretty(writer, value).map_err(FormatError::Error),
-    #[cfg(feature = "ron")]
-    Format::Ron => ron::to_writer_pretty(writer, value).map_err(FormatError::Error),
-    #[cfg(feature = "toml")]
-    Format::Toml => toml::to_writer_pretty(writer, value).map_err(FormatError::Error),
+  match format.into() {
+    Configured::Default(format) => match format {
+      #[cfg(feature = "json")]
+      Format::Json => json::to_writer_pretty(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::to_writer_pretty(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::to_writer_pretty(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::to_writer_pretty(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::to_writer_pretty(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::to_writer_pretty(writer, value).map_err(FormatError::Error),
+      #[allow(unreachable_patterns)]
+      _ => Err(unsupported(format, Feature::Pretty))
+    },
     #[allow(unreachable_patterns)]
-    _ => Err(unsupported(format, Feature::Pretty))
+    configured => configured.stream(Feature::Pretty)?.to_writer_pretty(writer, value).map_err(FormatError::Error)
   }
 }
 
-pub fn to_writer<W, T>(format: Format, writer: W, value: &T) -> Result<(), FormatError>
+pub fn to_writer<W, T>(format: impl Into<Configured>, writer: W, value: &T) -> Result<(), FormatError>
 where W: Write, T: Serialize {
-  match format {
-    #[cfg(feature = "bincode")]
-    Format::Bincode => bincode::to_writer(writer, value).map_err(FormatError::Error),
-    #[cfg(feature = "cbor")]
-    Format::Cbor => cbor::to_writer(writer, value).map_err(FormatError::Error),
-    #[cfg(feature = "json")]
-    Format::Json => json::to_writer(writer, value).map_err(FormatError::Error),
-    #[cfg(feature = "messagepack")]
-    Format::MessagePack => messagepack::to_writer(writer, value).map_err(FormatError::Error),
-    #[cfg(feature = "ron")]
-    Format::Ron => ron::to_writer(writer, value).map_err(FormatError::Error),
-    #[cfg(feature = "toml")]
-    Format::Toml => toml::to_writer(writer, value).map_err(FormatError::Error),
-    #[cfg(feature = "xml")]
-    Format::Xml => xml::to_writer(writer, value).map_err(FormatError::Error),
+  match format.into() {
+    Configured::Default(format) => match format {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => bencode::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "bincode")]
+      Format::Bincode => bincode::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "bson")]
+      Format::Bson => bson::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "cbor")]
+      Format::Cbor => cbor::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "csv")]
+      Format::Csv => csv::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => flexbuffers::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "ini")]
+      Format::Ini => ini::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "json")]
+      Format::Json => json::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "messagepack")]
+      Format::MessagePack => messagepack::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "pickle")]
+      Format::Pickle => pickle::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "plist")]
+      Format::Plist => plist::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "postcard")]
+      Format::Postcard => postcard::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "properties")]
+      Format::Properties => properties::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => urlencoded::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::to_writer(writer, value).map_err(FormatError::Error),
+      #[cfg(feature = "yaml")]
+      Format::Yaml => yaml::to_writer(writer, value).map_err(FormatError::Error),
+      #[allow(unreachable_patterns)]
+      _ => Err(unsupported(format, Feature::Stream))
+    },
     #[allow(unreachable_patterns)]
-    _ => Err(unsupported(format, Feature::Stream))
+    configured => configured.stream(Feature::Stream)?.to_writer(writer, value).map_err(FormatError::Error)
   }
 }
 
-pub fn from_reader<R, T>(format: Format, reader: R) -> Result<T, FormatError>
+pub fn from_reader<R, T>(format: impl Into<Configured>, reader: R) -> Result<T, FormatError>
 where R: Read, T: DeserializeOwned {
-  match format {
-    #[cfg(feature = "bincode")]
-    Format::Bincode => bincode::from_reader(reader).map_err(FormatError::Error),
-    #[cfg(feature = "cbor")]
-    Format::Cbor => cbor::from_reader(reader).map_err(FormatError::Error),
-    #[cfg(feature = "json")]
-    Format::Json => json::from_reader(reader).map_err(FormatError::Error),
-    #[cfg(feature = "messagepack")]
-    Format::MessagePack => messagepack::from_reader(reader).map_err(FormatError::Error),
-    #[cfg(feature = "ron")]
-    Format::Ron => ron::from_reader(reader).map_err(FormatError::Error),
-    #[cfg(feature = "toml")]
-    Format::Toml => toml::from_reader(reader).map_err(FormatError::Error),
-    #[cfg(feature = "xml")]
-    Format::Xml => xml::from_reader(reader).map_err(FormatError::Error),
+  match format.into() {
+    Configured::Default(format) => match format {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => bencode::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "bincode")]
+      Format::Bincode => bincode::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "bson")]
+      Format::Bson => bson::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "cbor")]
+      Format::Cbor => cbor::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "csv")]
+      Format::Csv => csv::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => flexbuffers::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "ini")]
+      Format::Ini => ini::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "json")]
+      Format::Json => json::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "messagepack")]
+      Format::MessagePack => messagepack::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "pickle")]
+      Format::Pickle => pickle::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "plist")]
+      Format::Plist => plist::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "postcard")]
+      Format::Postcard => postcard::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "properties")]
+      Format::Properties => properties::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => urlencoded::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::from_reader(reader).map_err(FormatError::Error),
+      #[cfg(feature = "yaml")]
+      Format::Yaml => yaml::from_reader(reader).map_err(FormatError::Error),
+      #[allow(unreachable_patterns)]
+      _ => Err(unsupported(format, Feature::Stream))
+    },
+    #[allow(unreachable_patterns)]
+    configured => configured.stream(Feature::Stream)?.from_reader(reader).map_err(FormatError::Error)
+  }
+}
+
+pub fn from_reader_seed<R, S, V>(format: impl Into<Configured>, seed: S, reader: R) -> Result<V, FormatError>
+where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+  match format.into() {
+    Configured::Default(format) => match format {
+      #[cfg(feature = "bencode")]
+      Format::Bencode => bencode::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "bincode")]
+      Format::Bincode => bincode::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "bson")]
+      Format::Bson => bson::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "cbor")]
+      Format::Cbor => cbor::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "csv")]
+      Format::Csv => csv::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "flexbuffers")]
+      Format::FlexBuffers => flexbuffers::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "ini")]
+      Format::Ini => ini::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "json")]
+      Format::Json => json::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "json5")]
+      Format::Json5 => json5::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "messagepack")]
+      Format::MessagePack => messagepack::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "pickle")]
+      Format::Pickle => pickle::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "plist")]
+      Format::Plist => plist::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "postcard")]
+      Format::Postcard => postcard::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "properties")]
+      Format::Properties => properties::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "sexpr")]
+      Format::SExpr => sexpr::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "urlencoded")]
+      Format::UrlEncoded => urlencoded::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "xml")]
+      Format::Xml => xml::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[cfg(feature = "yaml")]
+      Format::Yaml => yaml::from_reader_seed(seed, reader).map_err(FormatError::Error),
+      #[allow(unreachable_patterns)]
+      _ => Err(unsupported(format, Feature::Stream))
+    },
     #[allow(unreachable_patterns)]
-    _ => Err(unsupported(format, Feature::Stream))
+    configured => configured.stream(Feature::Stream)?.from_reader_seed(seed, reader).map_err(FormatError::Error)
   }
 }
 
 pub fn extension(format: Format) -> Result<&'static str, Unsupported> {
   match format {
+    #[cfg(feature = "bencode")]
+    Format::Bencode => Ok(bencode::EXTENSION),
+    #[cfg(feature = "bincode")]
+    Format::Bincode => Ok(bincode::EXTENSION),
+    #[cfg(feature = "bson")]
+    Format::Bson => Ok(bson::EXTENSION),
+    #[cfg(feature = "cbor")]
+    Format::Cbor => Ok(cbor::EXTENSION),
+    #[cfg(feature = "csv")]
+    Format::Csv => Ok(csv::EXTENSION),
+    #[cfg(feature = "flexbuffers")]
+    Format::FlexBuffers => Ok(flexbuffers::EXTENSION),
+    #[cfg(feature = "ini")]
+    Format::Ini => Ok(ini::EXTENSION),
     #[cfg(feature = "json")]
     Format::Json => Ok(json::EXTENSION),
+    #[cfg(feature = "json5")]
+    Format::Json5 => Ok(json5::EXTENSION),
+    #[cfg(feature = "messagepack")]
+    Format::MessagePack => Ok(messagepack::EXTENSION),
+    #[cfg(feature = "pickle")]
+    Format::Pickle => Ok(pickle::EXTENSION),
+    #[cfg(feature = "plist")]
+    Format::Plist => Ok(plist::EXTENSION),
+    #[cfg(feature = "postcard")]
+    Format::Postcard => Ok(postcard::EXTENSION),
+    #[cfg(feature = "properties")]
+    Format::Properties => Ok(properties::EXTENSION),
     #[cfg(feature = "ron")]
     Format::Ron => Ok(ron::EXTENSION),
+    #[cfg(feature = "sexpr")]
+    Format::SExpr => Ok(sexpr::EXTENSION),
     #[cfg(feature = "toml")]
     Format::Toml => Ok(toml::EXTENSION),
+    #[cfg(feature = "urlencoded")]
+    Format::UrlEncoded => Ok(urlencoded::EXTENSION),
     #[cfg(feature = "xml")]
     Format::Xml => Ok(xml::EXTENSION),
+    #[cfg(feature = "yaml")]
+    Format::Yaml => Ok(yaml::EXTENSION),
     #[allow(unreachable_patterns)]
     _ => Err(Unsupported { format, feature: Feature::Extension })
   }
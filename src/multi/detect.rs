@@ -0,0 +1,244 @@
+//! Content sniffing, for guessing the format of unlabeled data.
+
+use serde::de::{Deserialize, IgnoredAny};
+use std::cmp::Reverse;
+use std::fmt::{self, Display};
+
+use super::{from_slice, Format, FormatError};
+
+/// How confident [`detect_ranked`] is that some data is in a given format.
+///
+/// [`detect_ranked`]: ./fn.detect_ranked.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Confidence {
+  /// The data parses as this format, but has nothing else in common with it.
+  Possible,
+  /// The data parses as this format, and starts the way this format usually does.
+  Likely,
+  /// The data parses as this format, and starts with a marker unique to it.
+  Certain
+}
+
+/// Guesses the format of the given data, returning the most likely candidate.
+///
+/// See [`detect_ranked`] for details on how formats are detected.
+///
+/// [`detect_ranked`]: ./fn.detect_ranked.html
+#[inline]
+pub fn detect(data: &[u8]) -> Option<Format> {
+  detect_ranked(data).first().map(|&(format, _)| format)
+}
+
+/// Guesses the format of the given data, returning every candidate from most to least likely.
+///
+/// Candidates are ranked by magic bytes and leading tokens, and any format the data
+/// fails to parse as is discarded. When the data is valid UTF-8, text formats are ranked above
+/// binary ones of the same confidence, since short text such as `1` is often valid binary data too. Formats that are not self-describing,
+/// such as Bincode, cannot be parsed without knowing the type and are never detected.
+pub fn detect_ranked(data: &[u8]) -> Vec<(Format, Confidence)> {
+  if data.iter().all(u8::is_ascii_whitespace) {
+    return Vec::new();
+  }
+
+  let is_utf8 = std::str::from_utf8(data).is_ok();
+  let mut candidates = Format::iter()
+    .filter(|&format| format.is_self_describing() && parses(format, data))
+    .map(|format| (format, sniff(format, data).unwrap_or(Confidence::Possible)))
+    .collect::<Vec<_>>();
+  candidates.sort_by_key(|&(format, confidence)| (Reverse(confidence), is_utf8 && !format.is_text()));
+  candidates
+}
+
+/// Deserializes a value from a `&[u8]`, detecting its format with [`detect_ranked`].
+///
+/// Each candidate is tried in order until one succeeds. If none succeed, the error from the
+/// most likely candidate is returned, or [`Undetected`] if there were no candidates at all.
+///
+/// [`detect_ranked`]: ./fn.detect_ranked.html
+/// [`Undetected`]: ./struct.Undetected.html
+pub fn from_slice_auto<'d, T>(data: &'d [u8]) -> Result<T, FormatError>
+where T: Deserialize<'d> {
+  let mut first_error = None;
+  for (format, _) in detect_ranked(data) {
+    match from_slice(format, data) {
+      Ok(value) => return Ok(value),
+      Err(error) => {
+        first_error.get_or_insert(error);
+      },
+    }
+  }
+
+  Err(first_error.unwrap_or_else(|| FormatError::Error(Box::new(Undetected))))
+}
+
+/// An error returned by [`from_slice_auto`] when the data did not match any enabled format.
+///
+/// [`from_slice_auto`]: ./fn.from_slice_auto.html
+#[derive(Debug, Copy, Clone)]
+pub struct Undetected;
+
+impl Display for Undetected {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "could not detect the format of the data")
+  }
+}
+
+impl std::error::Error for Undetected {}
+
+/// Checks whether the data parses as the given format, without any trailing data.
+fn parses(format: Format, data: &[u8]) -> bool {
+  match format {
+    // `rmp_serde` ignores trailing data, which would make almost anything parse as MessagePack
+    #[cfg(feature = "messagepack")]
+    Format::MessagePack => {
+      let mut deserializer = rmp_serde::Deserializer::new(std::io::Cursor::new(data));
+      IgnoredAny::deserialize(&mut deserializer).is_ok() && deserializer.position() == data.len() as u64
+    },
+    #[allow(unreachable_patterns)]
+    _ => from_slice::<IgnoredAny>(format, data).is_ok()
+  }
+}
+
+/// Checks how closely the start of the data resembles the given format.
+#[allow(unused_variables)]
+fn sniff(format: Format, data: &[u8]) -> Option<Confidence> {
+  let text = std::str::from_utf8(data).ok().map(|text| text.trim_start_matches('\u{feff}').trim_start());
+  match format {
+    #[cfg(feature = "bencode")]
+    Format::Bencode => match data {
+      // dictionaries start with `d` and a key's length, and end with `e`
+      [b'd', b'0'..=b'9', .., b'e'] => Some(Confidence::Likely),
+      _ => None
+    },
+    #[cfg(feature = "bson")]
+    Format::Bson => match data {
+      // documents start with their own length and end with a null byte
+      [a, b, c, d, .., 0x00] if u32::from_le_bytes([*a, *b, *c, *d]) as usize == data.len() => Some(Confidence::Likely),
+      _ => None
+    },
+    #[cfg(feature = "cbor")]
+    Format::Cbor => match data {
+      [0xd9, 0xd9, 0xf7, ..] => Some(Confidence::Certain),
+      [0x80..=0xbf, ..] => Some(Confidence::Likely),
+      _ => None
+    },
+    #[cfg(feature = "ini")]
+    Format::Ini => {
+      let line = text?.lines().map(str::trim).find(|line| !line.is_empty() && !line.starts_with('#'))?;
+      if line.starts_with(';') || (line.starts_with('[') && line.ends_with(']')) {
+        Some(Confidence::Likely)
+      } else {
+        None
+      }
+    },
+    #[cfg(feature = "json")]
+    Format::Json => match text?.as_bytes().first()? {
+      b'{' | b'[' => Some(Confidence::Likely),
+      _ => None
+    },
+    #[cfg(feature = "json5")]
+    Format::Json5 => {
+      let text = text?;
+      if text.starts_with("//") || text.starts_with("/*") {
+        Some(Confidence::Likely)
+      } else {
+        None
+      }
+    },
+    #[cfg(feature = "messagepack")]
+    Format::MessagePack => match data.first()? {
+      0x80..=0x9f | 0xdc..=0xdf => Some(Confidence::Likely),
+      _ => None
+    },
+    #[cfg(feature = "pickle")]
+    Format::Pickle => match data {
+      // protocol 2 and later start with the `PROTO` opcode, and every pickle ends with `STOP`
+      [0x80, 0x02..=0x05, .., b'.'] => Some(Confidence::Certain),
+      [.., b'.'] => Some(Confidence::Likely),
+      _ => None
+    },
+    #[cfg(feature = "plist")]
+    Format::Plist => {
+      let is_xml = text.is_some_and(|text| text.starts_with("<?xml") && text.contains("<plist"));
+      if data.starts_with(b"bplist00") || is_xml {
+        Some(Confidence::Certain)
+      } else {
+        None
+      }
+    },
+    #[cfg(feature = "ron")]
+    Format::Ron => {
+      let text = text?;
+      let name = text.split('(').next().unwrap_or_default();
+      if text.starts_with("#![enable(") {
+        Some(Confidence::Certain)
+      } else if text.len() > name.len() && name.chars().all(|ch| ch.is_alphanumeric() || ch == '_') {
+        Some(Confidence::Likely)
+      } else {
+        None
+      }
+    },
+    #[cfg(feature = "sexpr")]
+    Format::SExpr => match text?.as_bytes().first()? {
+      b'(' | b';' => Some(Confidence::Likely),
+      _ => None
+    },
+    #[cfg(feature = "toml")]
+    Format::Toml => {
+      let line = text?.lines().map(str::trim).find(|line| !line.is_empty() && !line.starts_with('#'))?;
+      let is_table = line.starts_with('[') && line.ends_with(']');
+      let is_key = line.split_once('=').is_some_and(|(key, _)| {
+        let key = key.trim();
+        !key.is_empty() && key.chars().all(|ch| ch.is_alphanumeric() || "_-.\"' ".contains(ch))
+      });
+
+      if is_table || is_key {
+        Some(Confidence::Likely)
+      } else {
+        None
+      }
+    },
+    #[cfg(feature = "urlencoded")]
+    Format::UrlEncoded => {
+      let text = text?;
+      let is_pair = |pair: &str| pair.split_once('=').is_some_and(|(key, value)| {
+        !key.is_empty() && key.chars().chain(value.chars()).all(|ch| ch.is_ascii_alphanumeric() || "-._~%+".contains(ch))
+      });
+
+      if text.trim_end().split('&').all(is_pair) {
+        Some(Confidence::Likely)
+      } else {
+        None
+      }
+    },
+    #[cfg(feature = "xml")]
+    Format::Xml => {
+      let text = text?;
+      if text.starts_with("<?xml") {
+        Some(Confidence::Certain)
+      } else if text.starts_with('<') {
+        Some(Confidence::Likely)
+      } else {
+        None
+      }
+    },
+    #[cfg(feature = "yaml")]
+    Format::Yaml => {
+      let text = text?;
+      let line = text.lines().map(str::trim_end).find(|line| !line.is_empty() && !line.starts_with('#'))?;
+      let is_key = line.split_once(": ").or_else(|| line.strip_suffix(':').map(|key| (key, "")))
+        .is_some_and(|(key, _)| !key.is_empty() && !key.starts_with(['{', '[', '"', '\'']));
+
+      if text.starts_with("%YAML") {
+        Some(Confidence::Certain)
+      } else if line == "---" || line.starts_with("--- ") || line.starts_with("- ") || is_key {
+        Some(Confidence::Likely)
+      } else {
+        None
+      }
+    },
+    #[allow(unreachable_patterns)]
+    _ => None
+  }
+}
@@ -0,0 +1,503 @@
+//! A runtime registry of formats, for looking up format handlers by name, extension or MIME type.
+//!
+//! Unlike [`Format`], which can only ever represent the formats built into this crate,
+//! a [`FormatRegistry`] can be extended with user-defined formats at runtime.
+//! The functions in [`multi`] only know about built-in formats, so registered formats are used
+//! through the registry's own `to_*` and `from_*` methods, which dispatch by name instead.
+//! Registering a configured format under a built-in name, such as `Cbor::new().with_packed(true)`
+//! under `"cbor"`, overrides the default options of that format for the registry.
+//!
+//! ## Example Usage
+//! ```rust
+//! use serde::de::{Deserialize, DeserializeSeed};
+//! use serde::ser::Serialize;
+//! use serde_multi::formats::json;
+//! use serde_multi::registry::{FormatEntry, FormatRegistry};
+//! use serde_multi::{Error, SerdeBytes};
+//!
+//! /// JSON prefixed with a magic number.
+//! struct MyFormat;
+//!
+//! impl MyFormat {
+//!   fn payload<'d>(&self, data: &'d [u8]) -> Result<&'d [u8], Error> {
+//!     data.strip_prefix(b"MYF").ok_or_else(|| "missing magic number".into())
+//!   }
+//! }
+//!
+//! impl SerdeBytes for MyFormat {
+//!   fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, Error>
+//!   where T: Serialize {
+//!     let mut data = b"MYF".to_vec();
+//!     data.extend(json::to_vec(value)?);
+//!     Ok(data)
+//!   }
+//!
+//!   fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, Error>
+//!   where T: Deserialize<'d> {
+//!     json::from_slice(self.payload(data)?)
+//!   }
+//!
+//!   // the registry deserializes through this method
+//!   fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, Error>
+//!   where S: DeserializeSeed<'d> {
+//!     json::from_slice_seed(seed, self.payload(data)?)
+//!   }
+//! }
+//!
+//! let mut registry = FormatRegistry::new();
+//! registry.register(FormatEntry::new("myformat", MyFormat).with_extension("myf"));
+//!
+//! let entry = registry.get_by_extension("myf").expect("format was not registered");
+//! assert_eq!(entry.name(), "myformat");
+//!
+//! let data = registry.to_vec("myformat", &vec![1, 2]).expect("failed to serialize");
+//! assert_eq!(data, b"MYF[1,2]");
+//! assert_eq!(registry.from_slice::<Vec<i32>>("myformat", &data).expect("failed to deserialize"), vec![1, 2]);
+//! ```
+//!
+//! [`Format`]: ../multi/enum.Format.html
+//! [`multi`]: ../multi/index.html
+//! [`FormatRegistry`]: ./struct.FormatRegistry.html
+
+use serde::de::{Deserialize, DeserializeOwned};
+use serde::ser::Serialize;
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display};
+use std::io::{Read, Write};
+use std::path::Path;
+
+#[cfg(feature = "bencode")]
+use crate::formats::bencode;
+#[cfg(feature = "bincode")]
+use crate::formats::bincode;
+#[cfg(feature = "bson")]
+use crate::formats::bson;
+#[cfg(feature = "cbor")]
+use crate::formats::cbor;
+#[cfg(feature = "csv")]
+use crate::formats::csv;
+#[cfg(feature = "flexbuffers")]
+use crate::formats::flexbuffers;
+#[cfg(feature = "ini")]
+use crate::formats::ini;
+#[cfg(feature = "json")]
+use crate::formats::json;
+#[cfg(feature = "json5")]
+use crate::formats::json5;
+#[cfg(feature = "messagepack")]
+use crate::formats::messagepack;
+#[cfg(feature = "pickle")]
+use crate::formats::pickle;
+#[cfg(feature = "plist")]
+use crate::formats::plist;
+#[cfg(feature = "postcard")]
+use crate::formats::postcard;
+#[cfg(feature = "properties")]
+use crate::formats::properties;
+#[cfg(feature = "ron")]
+use crate::formats::ron;
+#[cfg(feature = "sexpr")]
+use crate::formats::sexpr;
+#[cfg(feature = "toml")]
+use crate::formats::toml;
+#[cfg(feature = "urlencoded")]
+use crate::formats::urlencoded;
+#[cfg(feature = "xml")]
+use crate::formats::xml;
+#[cfg(feature = "yaml")]
+use crate::formats::yaml;
+
+use crate::multi::{Feature, Format};
+use crate::traits::{DynSerdeBytes, DynSerdeStream, DynSerdeText};
+
+/// A single format registered in a [`FormatRegistry`], holding its handlers and metadata.
+///
+/// [`FormatRegistry`]: ./struct.FormatRegistry.html
+pub struct FormatEntry {
+  name: String,
+  aliases: Vec<String>,
+  format: Option<Format>,
+  extensions: Vec<String>,
+  mime_types: Vec<String>,
+  bytes: Box<dyn DynSerdeBytes>,
+  text: Option<Box<dyn DynSerdeText>>,
+  stream: Option<Box<dyn DynSerdeStream>>
+}
+
+impl FormatEntry {
+  /// Creates a new entry with the given name and binary handler.
+  /// Text and stream handlers, extensions and MIME types can be added with the `with_*` methods.
+  ///
+  /// **Handlers must override the `from_*_seed` methods** of [`SerdeText`], [`SerdeBytes`] and [`SerdeStream`]:
+  /// the registry deserializes through them, and a handler that keeps their default bodies
+  /// compiles but fails to deserialize anything.
+  ///
+  /// [`SerdeText`]: ../traits/trait.SerdeText.html
+  /// [`SerdeBytes`]: ../traits/trait.SerdeBytes.html
+  /// [`SerdeStream`]: ../traits/trait.SerdeStream.html
+  pub fn new<F>(name: impl Into<String>, format: F) -> Self
+  where F: DynSerdeBytes + 'static {
+    FormatEntry {
+      name: normalize(&name.into()),
+      aliases: Vec::new(),
+      format: None,
+      extensions: Vec::new(),
+      mime_types: Vec::new(),
+      bytes: Box::new(format),
+      text: None,
+      stream: None
+    }
+  }
+
+  /// Sets the handler used for text serialization/deserialization.
+  pub fn with_text<F>(mut self, format: F) -> Self
+  where F: DynSerdeText + 'static {
+    self.text = Some(Box::new(format));
+    self
+  }
+
+  /// Sets the handler used for stream serialization/deserialization.
+  pub fn with_stream<F>(mut self, format: F) -> Self
+  where F: DynSerdeStream + 'static {
+    self.stream = Some(Box::new(format));
+    self
+  }
+
+  /// Adds another name this format can be looked up by.
+  pub fn with_alias(mut self, alias: impl Into<String>) -> Self {
+    self.aliases.push(normalize(&alias.into()));
+    self
+  }
+
+  /// Adds a file extension, with or without the leading `.`.
+  /// The first extension added is considered the primary one.
+  pub fn with_extension(mut self, extension: impl Into<String>) -> Self {
+    self.extensions.push(normalize_extension(&extension.into()));
+    self
+  }
+
+  /// Adds a MIME type. The first MIME type added is considered the primary one.
+  pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+    self.mime_types.push(normalize_mime_type(&mime_type.into()));
+    self
+  }
+
+  /// The name this format is registered under.
+  #[inline]
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// The other names this format can be looked up by.
+  #[inline]
+  pub fn aliases(&self) -> impl Iterator<Item = &str> {
+    self.aliases.iter().map(String::as_str)
+  }
+
+  /// The built-in [`Format`] this entry corresponds to, if any.
+  ///
+  /// [`Format`]: ../multi/enum.Format.html
+  #[inline]
+  pub fn format(&self) -> Option<Format> {
+    self.format
+  }
+
+  /// The primary file extension of this format, if it has any.
+  #[inline]
+  pub fn extension(&self) -> Option<&str> {
+    self.extensions.first().map(String::as_str)
+  }
+
+  /// All file extensions of this format.
+  #[inline]
+  pub fn extensions(&self) -> impl Iterator<Item = &str> {
+    self.extensions.iter().map(String::as_str)
+  }
+
+  /// The primary MIME type of this format, if it has any.
+  #[inline]
+  pub fn mime_type(&self) -> Option<&str> {
+    self.mime_types.first().map(String::as_str)
+  }
+
+  /// All MIME types of this format.
+  #[inline]
+  pub fn mime_types(&self) -> impl Iterator<Item = &str> {
+    self.mime_types.iter().map(String::as_str)
+  }
+
+  /// The binary handler of this format.
+  #[inline]
+  pub fn bytes(&self) -> &dyn DynSerdeBytes {
+    &*self.bytes
+  }
+
+  /// The text handler of this format, if it supports text serialization/deserialization.
+  #[inline]
+  pub fn text(&self) -> Option<&dyn DynSerdeText> {
+    self.text.as_deref()
+  }
+
+  /// The stream handler of this format, if it supports stream serialization/deserialization.
+  #[inline]
+  pub fn stream(&self) -> Option<&dyn DynSerdeStream> {
+    self.stream.as_deref()
+  }
+}
+
+impl Debug for FormatEntry {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("FormatEntry")
+      .field("name", &self.name)
+      .field("aliases", &self.aliases)
+      .field("format", &self.format)
+      .field("extensions", &self.extensions)
+      .field("mime_types", &self.mime_types)
+      .field("text", &self.text.is_some())
+      .field("stream", &self.stream.is_some())
+      .finish()
+  }
+}
+
+/// A collection of formats that can be looked up by name, file extension or MIME type.
+///
+/// When multiple entries share a name, extension or MIME type, the one registered last takes precedence,
+/// so built-in formats can be overridden by registering a replacement.
+#[derive(Debug, Default)]
+pub struct FormatRegistry {
+  entries: Vec<FormatEntry>,
+  names: HashMap<String, usize>,
+  extensions: HashMap<String, usize>,
+  mime_types: HashMap<String, usize>
+}
+
+impl FormatRegistry {
+  /// Creates a registry containing every built-in format enabled by this crate's features.
+  pub fn new() -> Self {
+    let mut registry = FormatRegistry::empty();
+    for format in Format::iter() {
+      registry.register(builtin_entry(format));
+    }
+
+    registry
+  }
+
+  /// Creates a registry containing no formats.
+  #[inline]
+  pub fn empty() -> Self {
+    FormatRegistry::default()
+  }
+
+  /// Adds a format to this registry.
+  pub fn register(&mut self, entry: FormatEntry) {
+    let index = self.entries.len();
+    self.names.insert(entry.name.clone(), index);
+    for alias in entry.aliases.iter() {
+      self.names.insert(alias.clone(), index);
+    }
+
+    for extension in entry.extensions.iter() {
+      self.extensions.insert(extension.clone(), index);
+    }
+
+    for mime_type in entry.mime_types.iter() {
+      self.mime_types.insert(mime_type.clone(), index);
+    }
+
+    self.entries.push(entry);
+  }
+
+  /// Looks up a format by name or alias, ignoring case.
+  pub fn get(&self, name: &str) -> Option<&FormatEntry> {
+    self.names.get(&normalize(name)).map(|&index| &self.entries[index])
+  }
+
+  /// Looks up a format by file extension, with or without the leading `.`, ignoring case.
+  pub fn get_by_extension(&self, extension: &str) -> Option<&FormatEntry> {
+    let extension = normalize_extension(extension);
+    self.extensions.get(&extension).map(|&index| &self.entries[index])
+  }
+
+  /// Looks up a format by MIME type, ignoring case and any parameters such as `charset`.
+  pub fn get_by_mime_type(&self, mime_type: &str) -> Option<&FormatEntry> {
+    let mime_type = normalize_mime_type(mime_type);
+    self.mime_types.get(&mime_type).map(|&index| &self.entries[index])
+  }
+
+  /// Looks up a format by the extension of the given path.
+  pub fn get_by_path(&self, path: impl AsRef<Path>) -> Option<&FormatEntry> {
+    let extension = path.as_ref().extension()?.to_str()?;
+    self.get_by_extension(extension)
+  }
+
+  /// Iterates over every format in this registry, in the order they were registered.
+  /// Entries that have been overridden by a later registration are still included.
+  #[inline]
+  pub fn iter(&self) -> impl Iterator<Item = &FormatEntry> {
+    self.entries.iter()
+  }
+
+  /// Pretty-print serializes a value to a `String` with the format registered under the given name.
+  pub fn to_string_pretty<T>(&self, name: &str, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    self.text(name)?.to_string_pretty(value)
+  }
+
+  /// Serializes a value to a `String` with the format registered under the given name.
+  pub fn to_string<T>(&self, name: &str, value: &T) -> Result<String, crate::Error>
+  where T: Serialize {
+    self.text(name)?.to_string(value)
+  }
+
+  /// Deserializes a value from a `str` with the format registered under the given name.
+  pub fn from_str<'d, T>(&self, name: &str, data: &'d str) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    self.text(name)?.from_str(data)
+  }
+
+  /// Pretty-print serializes a value to a `Vec<u8>` with the format registered under the given name.
+  pub fn to_vec_pretty<T>(&self, name: &str, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    self.bytes(name)?.to_vec_pretty(value)
+  }
+
+  /// Serializes a value to a `Vec<u8>` with the format registered under the given name.
+  pub fn to_vec<T>(&self, name: &str, value: &T) -> Result<Vec<u8>, crate::Error>
+  where T: Serialize {
+    self.bytes(name)?.to_vec(value)
+  }
+
+  /// Deserializes a value from a `&[u8]` with the format registered under the given name.
+  pub fn from_slice<'d, T>(&self, name: &str, data: &'d [u8]) -> Result<T, crate::Error>
+  where T: Deserialize<'d> {
+    self.bytes(name)?.from_slice(data)
+  }
+
+  /// Pretty-print serializes a value into a `Write` stream with the format registered under the given name.
+  pub fn to_writer_pretty<W, T>(&self, name: &str, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    self.stream(name)?.to_writer_pretty(writer, value)
+  }
+
+  /// Serializes a value into a `Write` stream with the format registered under the given name.
+  pub fn to_writer<W, T>(&self, name: &str, writer: W, value: &T) -> Result<(), crate::Error>
+  where W: Write, T: Serialize {
+    self.stream(name)?.to_writer(writer, value)
+  }
+
+  /// Deserializes a value from a `Read` stream with the format registered under the given name.
+  pub fn from_reader<R, T>(&self, name: &str, reader: R) -> Result<T, crate::Error>
+  where R: Read, T: DeserializeOwned {
+    self.stream(name)?.from_reader(reader)
+  }
+
+  fn entry(&self, name: &str) -> Result<&FormatEntry, RegistryError> {
+    self.get(name).ok_or_else(|| RegistryError::Unregistered(name.to_owned()))
+  }
+
+  fn text(&self, name: &str) -> Result<&dyn DynSerdeText, RegistryError> {
+    let entry = self.entry(name)?;
+    entry.text().ok_or_else(|| RegistryError::Unsupported(entry.name.clone(), Feature::Text))
+  }
+
+  fn bytes(&self, name: &str) -> Result<&dyn DynSerdeBytes, RegistryError> {
+    self.entry(name).map(FormatEntry::bytes)
+  }
+
+  fn stream(&self, name: &str) -> Result<&dyn DynSerdeStream, RegistryError> {
+    let entry = self.entry(name)?;
+    entry.stream().ok_or_else(|| RegistryError::Unsupported(entry.name.clone(), Feature::Stream))
+  }
+}
+
+/// An error caused by one of the `to_*` or `from_*` methods of a [`FormatRegistry`].
+///
+/// [`FormatRegistry`]: ./struct.FormatRegistry.html
+#[derive(Debug, Clone)]
+pub enum RegistryError {
+  /// No format is registered under the given name.
+  Unregistered(String),
+  /// The named format does not support the given feature.
+  Unsupported(String, Feature)
+}
+
+impl Display for RegistryError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      RegistryError::Unregistered(name) => write!(f, "no format registered as `{}`", name),
+      RegistryError::Unsupported(name, feature) => write!(f, "{} does not support {}", name, feature)
+    }
+  }
+}
+
+impl std::error::Error for RegistryError {}
+
+#[allow(unreachable_code, unused_variables)]
+fn builtin_entry(format: Format) -> FormatEntry {
+  let (&name, aliases) = format.aliases().split_first().expect("formats have a name");
+  let entry: FormatEntry = match format {
+    #[cfg(feature = "bencode")]
+    Format::Bencode => FormatEntry::new(name, bencode::Bencode).with_stream(bencode::Bencode),
+    #[cfg(feature = "bincode")]
+    Format::Bincode => FormatEntry::new(name, bincode::Bincode::new()).with_stream(bincode::Bincode::new()),
+    #[cfg(feature = "bson")]
+    Format::Bson => FormatEntry::new(name, bson::Bson).with_stream(bson::Bson),
+    #[cfg(feature = "cbor")]
+    Format::Cbor => FormatEntry::new(name, cbor::Cbor::new()).with_stream(cbor::Cbor::new()),
+    #[cfg(feature = "csv")]
+    Format::Csv => FormatEntry::new(name, csv::Csv::new()).with_text(csv::Csv::new()).with_stream(csv::Csv::new()),
+    #[cfg(feature = "flexbuffers")]
+    Format::FlexBuffers => FormatEntry::new(name, flexbuffers::FlexBuffers).with_stream(flexbuffers::FlexBuffers),
+    #[cfg(feature = "ini")]
+    Format::Ini => FormatEntry::new(name, ini::Ini).with_text(ini::Ini).with_stream(ini::Ini),
+    #[cfg(feature = "json")]
+    Format::Json => FormatEntry::new(name, json::Json::new()).with_text(json::Json::new()).with_stream(json::Json::new()),
+    #[cfg(feature = "json5")]
+    Format::Json5 => FormatEntry::new(name, json5::Json5::new()).with_text(json5::Json5::new()).with_stream(json5::Json5::new()),
+    #[cfg(feature = "messagepack")]
+    Format::MessagePack => FormatEntry::new(name, messagepack::MessagePack::new()).with_stream(messagepack::MessagePack::new()),
+    #[cfg(feature = "pickle")]
+    Format::Pickle => FormatEntry::new(name, pickle::Pickle::new()).with_stream(pickle::Pickle::new()),
+    #[cfg(feature = "plist")]
+    Format::Plist => FormatEntry::new(name, plist::Plist::new()).with_text(plist::Plist::new()).with_stream(plist::Plist::new()),
+    #[cfg(feature = "postcard")]
+    Format::Postcard => FormatEntry::new(name, postcard::Postcard::new()).with_stream(postcard::Postcard::new()),
+    #[cfg(feature = "properties")]
+    Format::Properties => FormatEntry::new(name, properties::Properties).with_text(properties::Properties).with_stream(properties::Properties),
+    #[cfg(feature = "ron")]
+    Format::Ron => FormatEntry::new(name, ron::Ron::new()).with_text(ron::Ron::new()).with_stream(ron::Ron::new()),
+    #[cfg(feature = "sexpr")]
+    Format::SExpr => FormatEntry::new(name, sexpr::SExpr).with_text(sexpr::SExpr).with_stream(sexpr::SExpr),
+    #[cfg(feature = "toml")]
+    Format::Toml => FormatEntry::new(name, toml::Toml::new()).with_text(toml::Toml::new()).with_stream(toml::Toml::new()),
+    #[cfg(feature = "urlencoded")]
+    Format::UrlEncoded => FormatEntry::new(name, urlencoded::UrlEncoded).with_text(urlencoded::UrlEncoded).with_stream(urlencoded::UrlEncoded),
+    #[cfg(feature = "xml")]
+    Format::Xml => FormatEntry::new(name, xml::Xml::new()).with_text(xml::Xml::new()).with_stream(xml::Xml::new()),
+    #[cfg(feature = "yaml")]
+    Format::Yaml => FormatEntry::new(name, yaml::Yaml).with_text(yaml::Yaml).with_stream(yaml::Yaml),
+    #[allow(unreachable_patterns)]
+    _ => panic!("unsupported")
+  };
+
+  let entry = aliases.iter().fold(entry, |entry, &alias| entry.with_alias(alias));
+  let entry = format.extensions().iter().fold(entry, |entry, &extension| entry.with_extension(extension));
+  let entry = format.mime_types().iter().fold(entry, |entry, &mime_type| entry.with_mime_type(mime_type));
+  FormatEntry { format: Some(format), ..entry }
+}
+
+#[inline]
+fn normalize(name: &str) -> String {
+  name.to_ascii_lowercase()
+}
+
+#[inline]
+fn normalize_extension(extension: &str) -> String {
+  normalize(extension.trim_start_matches('.'))
+}
+
+#[inline]
+fn normalize_mime_type(mime_type: &str) -> String {
+  let essence = mime_type.split(';').next().unwrap_or_default();
+  normalize(essence.trim())
+}
@@ -1,7 +1,18 @@
 #![allow(unused_variables)]
 //! Traits for standardizing the API.
+//!
+//! [`SerdeText`], [`SerdeBytes`] and [`SerdeStream`] are generic over the values being serialized,
+//! so they cannot be used as trait objects. [`DynSerdeText`], [`DynSerdeBytes`] and [`DynSerdeStream`]
+//! are their object-safe counterparts, and are implemented for every type implementing the former.
+//!
+//! [`SerdeText`]: ./trait.SerdeText.html
+//! [`SerdeBytes`]: ./trait.SerdeBytes.html
+//! [`SerdeStream`]: ./trait.SerdeStream.html
+//! [`DynSerdeText`]: ./trait.DynSerdeText.html
+//! [`DynSerdeBytes`]: ./trait.DynSerdeBytes.html
+//! [`DynSerdeStream`]: ./trait.DynSerdeStream.html
 
-use serde::de::{Deserialize, DeserializeOwned};
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed, Deserializer, Error as _};
 use serde::ser::Serialize;
 use std::io::{Read, Write};
 
@@ -23,8 +34,20 @@ pub trait SerdeText: SerdeBytes {
   where T: Serialize, Self: Sized;
 
   /// Deserialize a value from a `str`.
+  #[allow(clippy::wrong_self_convention)]
   fn from_str<'d, T>(&self, data: &'d str) -> Result<T, Error>
   where T: Deserialize<'d>, Self: Sized;
+
+  /// Deserialize a value from a `str` using the given `DeserializeSeed`.
+  /// Formats must override this to be usable as a [`DynSerdeText`], which returns an error otherwise.
+  ///
+  /// [`DynSerdeText`]: ./trait.DynSerdeText.html
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_str_seed<'d, S>(&self, seed: S, data: &'d str) -> Result<S::Value, Error>
+  where S: DeserializeSeed<'d>, Self: Sized {
+    Err(seed_unsupported())
+  }
 }
 
 /// A trait for serialization/deserialization to and from binary data.
@@ -43,8 +66,20 @@ pub trait SerdeBytes {
   where T: Serialize, Self: Sized;
 
   /// Deserialize this value from a `&[u8]`.
+  #[allow(clippy::wrong_self_convention)]
   fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, Error>
   where T: Deserialize<'d>, Self: Sized;
+
+  /// Deserialize a value from a `&[u8]` using the given `DeserializeSeed`.
+  /// Formats must override this to be usable as a [`DynSerdeBytes`], which returns an error otherwise.
+  ///
+  /// [`DynSerdeBytes`]: ./trait.DynSerdeBytes.html
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, Error>
+  where S: DeserializeSeed<'d>, Self: Sized {
+    Err(seed_unsupported())
+  }
 }
 
 /// A trait for serialization/deserialization to and from `Read` and `Write` streams.
@@ -62,11 +97,349 @@ pub trait SerdeStream: SerdeBytes {
   where W: Write, T: Serialize, Self: Sized;
 
   /// Deserialize a value from a `Read` stream.
+  #[allow(clippy::wrong_self_convention)]
   fn from_reader<R, T>(&self, reader: R) -> Result<T, Error>
   where R: Read, T: DeserializeOwned, Self: Sized;
+
+  /// Deserialize a value from a `Read` stream using the given `DeserializeSeed`.
+  /// Formats must override this to be usable as a [`DynSerdeStream`], which returns an error otherwise.
+  ///
+  /// [`DynSerdeStream`]: ./trait.DynSerdeStream.html
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_reader_seed<R, S, V>(&self, seed: S, reader: R) -> Result<V, Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V>, Self: Sized {
+    Err(seed_unsupported())
+  }
 }
 
 /// Allows a format to describe what extension it should have.
 pub trait Extension {
   fn extension(&self) -> &str;
 }
+
+/// Allows a format to describe every file extension and MIME type associated with it.
+pub trait Metadata: Extension {
+  /// Every file extension of this format, starting with the one returned by `extension`.
+  fn extensions(&self) -> &[&str];
+
+  /// File extensions this format is sometimes stored with, other than the one returned by `extension`.
+  #[inline]
+  fn alternate_extensions(&self) -> &[&str] {
+    self.extensions().get(1..).unwrap_or(&[])
+  }
+
+  /// The MIME type of this format. Formats without a registered MIME type use an `x-` prefixed one,
+  /// and formats that list no MIME types at all fall back to `application/octet-stream`.
+  #[inline]
+  fn mime_type(&self) -> &str {
+    self.mime_types().first().copied().unwrap_or("application/octet-stream")
+  }
+
+  /// Every MIME type of this format, starting with the one returned by `mime_type`.
+  fn mime_types(&self) -> &[&str];
+
+  /// MIME types this format is sometimes sent with, other than the one returned by `mime_type`.
+  #[inline]
+  fn alternate_mime_types(&self) -> &[&str] {
+    self.mime_types().get(1..).unwrap_or(&[])
+  }
+}
+
+/// A callback that receives a type-erased deserializer, used by the `erased_from_*` methods.
+pub type ErasedVisit<'a, 'd> = dyn FnMut(&mut dyn erased_serde::Deserializer<'d>) -> Result<(), erased_serde::Error> + 'a;
+
+/// A callback that receives a type-erased deserializer of any lifetime, used by `erased_from_reader`.
+pub type ErasedVisitOwned<'a> =
+  dyn for<'d> FnMut(&mut dyn erased_serde::Deserializer<'d>) -> Result<(), erased_serde::Error> + 'a;
+
+/// An object-safe version of [`SerdeText`].
+///
+/// This is implemented for every type that implements [`SerdeText`],
+/// and the generic convenience methods are available on `dyn DynSerdeText`.
+///
+/// # Deserialization needs `from_str_seed`
+///
+/// Every deserialization method of this trait is built on [`SerdeText::from_str_seed`].
+/// Formats that do not override it still implement this trait, but **fail to deserialize
+/// anything at runtime**, so user-defined formats meant to be used as a `dyn DynSerdeText`
+/// must implement it.
+///
+/// [`SerdeText`]: ./trait.SerdeText.html
+/// [`SerdeText::from_str_seed`]: ./trait.SerdeText.html#method.from_str_seed
+pub trait DynSerdeText: DynSerdeBytes {
+  /// Pretty-print serialize a type-erased value to a `String`.
+  fn erased_to_string_pretty(&self, value: &dyn erased_serde::Serialize) -> Result<String, Error>;
+
+  /// Serialize a type-erased value to a `String`.
+  fn erased_to_string(&self, value: &dyn erased_serde::Serialize) -> Result<String, Error>;
+
+  /// Build a deserializer from a `str` and pass it to `visit`.
+  fn erased_from_str<'d>(&self, data: &'d str, visit: &mut ErasedVisit<'_, 'd>) -> Result<(), Error>;
+}
+
+/// An object-safe version of [`SerdeBytes`].
+///
+/// This is implemented for every type that implements [`SerdeBytes`],
+/// and the generic convenience methods are available on `dyn DynSerdeBytes`.
+///
+/// # Deserialization needs `from_slice_seed`
+///
+/// Every deserialization method of this trait is built on [`SerdeBytes::from_slice_seed`].
+/// Formats that do not override it still implement this trait, but **fail to deserialize
+/// anything at runtime**, so user-defined formats meant to be used as a `dyn DynSerdeBytes`
+/// must implement it.
+///
+/// [`SerdeBytes`]: ./trait.SerdeBytes.html
+/// [`SerdeBytes::from_slice_seed`]: ./trait.SerdeBytes.html#method.from_slice_seed
+pub trait DynSerdeBytes: Send + Sync {
+  /// Pretty-print serialize a type-erased value to a `Vec<u8>`.
+  fn erased_to_vec_pretty(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, Error>;
+
+  /// Serialize a type-erased value to a `Vec<u8>`.
+  fn erased_to_vec(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, Error>;
+
+  /// Build a deserializer from a `&[u8]` and pass it to `visit`.
+  fn erased_from_slice<'d>(&self, data: &'d [u8], visit: &mut ErasedVisit<'_, 'd>) -> Result<(), Error>;
+}
+
+/// An object-safe version of [`SerdeStream`].
+///
+/// This is implemented for every type that implements [`SerdeStream`],
+/// and the generic convenience methods are available on `dyn DynSerdeStream`.
+///
+/// # Deserialization needs `from_reader_seed`
+///
+/// Every deserialization method of this trait is built on [`SerdeStream::from_reader_seed`].
+/// Formats that do not override it still implement this trait, but **fail to deserialize
+/// anything at runtime**, so user-defined formats meant to be used as a `dyn DynSerdeStream`
+/// must implement it.
+///
+/// [`SerdeStream`]: ./trait.SerdeStream.html
+/// [`SerdeStream::from_reader_seed`]: ./trait.SerdeStream.html#method.from_reader_seed
+pub trait DynSerdeStream: DynSerdeBytes {
+  /// Pretty-print serialize a type-erased value into a `Write` stream.
+  fn erased_to_writer_pretty(&self, writer: &mut dyn Write, value: &dyn erased_serde::Serialize) -> Result<(), Error>;
+
+  /// Serialize a type-erased value into a `Write` stream.
+  fn erased_to_writer(&self, writer: &mut dyn Write, value: &dyn erased_serde::Serialize) -> Result<(), Error>;
+
+  /// Build a deserializer from a `Read` stream and pass it to `visit`.
+  fn erased_from_reader(&self, reader: &mut dyn Read, visit: &mut ErasedVisitOwned<'_>) -> Result<(), Error>;
+}
+
+impl<F> DynSerdeText for F
+where F: SerdeText + Send + Sync {
+  #[inline]
+  fn erased_to_string_pretty(&self, value: &dyn erased_serde::Serialize) -> Result<String, Error> {
+    self.to_string_pretty(&value)
+  }
+
+  #[inline]
+  fn erased_to_string(&self, value: &dyn erased_serde::Serialize) -> Result<String, Error> {
+    self.to_string(&value)
+  }
+
+  #[inline]
+  fn erased_from_str<'d>(&self, data: &'d str, visit: &mut ErasedVisit<'_, 'd>) -> Result<(), Error> {
+    self.from_str_seed(ErasedSeed(visit), data)
+  }
+}
+
+impl<F> DynSerdeBytes for F
+where F: SerdeBytes + Send + Sync {
+  #[inline]
+  fn erased_to_vec_pretty(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, Error> {
+    self.to_vec_pretty(&value)
+  }
+
+  #[inline]
+  fn erased_to_vec(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, Error> {
+    self.to_vec(&value)
+  }
+
+  #[inline]
+  fn erased_from_slice<'d>(&self, data: &'d [u8], visit: &mut ErasedVisit<'_, 'd>) -> Result<(), Error> {
+    self.from_slice_seed(ErasedSeed(visit), data)
+  }
+}
+
+impl<F> DynSerdeStream for F
+where F: SerdeStream + Send + Sync {
+  #[inline]
+  fn erased_to_writer_pretty(&self, writer: &mut dyn Write, value: &dyn erased_serde::Serialize) -> Result<(), Error> {
+    self.to_writer_pretty(writer, &value)
+  }
+
+  #[inline]
+  fn erased_to_writer(&self, writer: &mut dyn Write, value: &dyn erased_serde::Serialize) -> Result<(), Error> {
+    self.to_writer(writer, &value)
+  }
+
+  #[inline]
+  fn erased_from_reader(&self, reader: &mut dyn Read, visit: &mut ErasedVisitOwned<'_>) -> Result<(), Error> {
+    self.from_reader_seed(ErasedSeedOwned(visit), reader)
+  }
+}
+
+impl<'a> dyn DynSerdeText + 'a {
+  /// Pretty-print serialize a value to a `String`.
+  #[inline]
+  pub fn to_string_pretty<T>(&self, value: &T) -> Result<String, Error>
+  where T: Serialize {
+    self.erased_to_string_pretty(value)
+  }
+
+  /// Serialize a value to a `String`.
+  #[inline]
+  pub fn to_string<T>(&self, value: &T) -> Result<String, Error>
+  where T: Serialize {
+    self.erased_to_string(value)
+  }
+
+  /// Deserialize a value from a `str`.
+  pub fn from_str<'d, T>(&self, data: &'d str) -> Result<T, Error>
+  where T: Deserialize<'d> {
+    let mut out = None;
+    self.erased_from_str(data, &mut |deserializer| {
+      out = Some(erased_serde::deserialize(deserializer)?);
+      Ok(())
+    })?;
+    out.ok_or_else(not_visited)
+  }
+
+  /// Deserialize a value from a `str` using the given `DeserializeSeed`.
+  pub fn from_str_seed<'d, S>(&self, seed: S, data: &'d str) -> Result<S::Value, Error>
+  where S: DeserializeSeed<'d> {
+    let mut seed = Some(seed);
+    let mut out = None;
+    self.erased_from_str(data, &mut |deserializer| {
+      if let Some(seed) = seed.take() {
+        out = Some(seed.deserialize(deserializer)?);
+      }
+
+      Ok(())
+    })?;
+    out.ok_or_else(not_visited)
+  }
+}
+
+impl<'a> dyn DynSerdeBytes + 'a {
+  /// Pretty-print serialize a value to a `Vec<u8>`.
+  #[inline]
+  pub fn to_vec_pretty<T>(&self, value: &T) -> Result<Vec<u8>, Error>
+  where T: Serialize {
+    self.erased_to_vec_pretty(value)
+  }
+
+  /// Serialize a value to a `Vec<u8>`.
+  #[inline]
+  pub fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, Error>
+  where T: Serialize {
+    self.erased_to_vec(value)
+  }
+
+  /// Deserialize a value from a `&[u8]`.
+  pub fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, Error>
+  where T: Deserialize<'d> {
+    let mut out = None;
+    self.erased_from_slice(data, &mut |deserializer| {
+      out = Some(erased_serde::deserialize(deserializer)?);
+      Ok(())
+    })?;
+    out.ok_or_else(not_visited)
+  }
+
+  /// Deserialize a value from a `&[u8]` using the given `DeserializeSeed`.
+  pub fn from_slice_seed<'d, S>(&self, seed: S, data: &'d [u8]) -> Result<S::Value, Error>
+  where S: DeserializeSeed<'d> {
+    let mut seed = Some(seed);
+    let mut out = None;
+    self.erased_from_slice(data, &mut |deserializer| {
+      if let Some(seed) = seed.take() {
+        out = Some(seed.deserialize(deserializer)?);
+      }
+
+      Ok(())
+    })?;
+    out.ok_or_else(not_visited)
+  }
+}
+
+impl<'a> dyn DynSerdeStream + 'a {
+  /// Pretty-print serialize a value into a `Write` stream.
+  #[inline]
+  pub fn to_writer_pretty<W, T>(&self, mut writer: W, value: &T) -> Result<(), Error>
+  where W: Write, T: Serialize {
+    self.erased_to_writer_pretty(&mut writer, value)
+  }
+
+  /// Serialize a value into a `Write` stream.
+  #[inline]
+  pub fn to_writer<W, T>(&self, mut writer: W, value: &T) -> Result<(), Error>
+  where W: Write, T: Serialize {
+    self.erased_to_writer(&mut writer, value)
+  }
+
+  /// Deserialize a value from a `Read` stream.
+  pub fn from_reader<R, T>(&self, mut reader: R) -> Result<T, Error>
+  where R: Read, T: DeserializeOwned {
+    let mut out = None;
+    self.erased_from_reader(&mut reader, &mut |deserializer| {
+      out = Some(erased_serde::deserialize(deserializer)?);
+      Ok(())
+    })?;
+    out.ok_or_else(not_visited)
+  }
+
+  /// Deserialize a value from a `Read` stream using the given `DeserializeSeed`.
+  pub fn from_reader_seed<R, S, V>(&self, seed: S, mut reader: R) -> Result<V, Error>
+  where R: Read, S: for<'d> DeserializeSeed<'d, Value = V> {
+    let mut seed = Some(seed);
+    let mut out = None;
+    self.erased_from_reader(&mut reader, &mut |deserializer| {
+      if let Some(seed) = seed.take() {
+        out = Some(seed.deserialize(deserializer)?);
+      }
+
+      Ok(())
+    })?;
+    out.ok_or_else(not_visited)
+  }
+}
+
+/// Adapts an [`ErasedVisit`] into a `DeserializeSeed`.
+struct ErasedSeed<'a, 'b, 'd>(&'a mut ErasedVisit<'b, 'd>);
+
+impl<'a, 'b, 'd> DeserializeSeed<'d> for ErasedSeed<'a, 'b, 'd> {
+  type Value = ();
+
+  #[inline]
+  fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+  where D: Deserializer<'d> {
+    (self.0)(&mut <dyn erased_serde::Deserializer>::erase(deserializer)).map_err(D::Error::custom)
+  }
+}
+
+/// Adapts an [`ErasedVisitOwned`] into a `DeserializeSeed` for any lifetime.
+struct ErasedSeedOwned<'a, 'b>(&'a mut ErasedVisitOwned<'b>);
+
+impl<'a, 'b, 'd> DeserializeSeed<'d> for ErasedSeedOwned<'a, 'b> {
+  type Value = ();
+
+  #[inline]
+  fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+  where D: Deserializer<'d> {
+    (self.0)(&mut <dyn erased_serde::Deserializer>::erase(deserializer)).map_err(D::Error::custom)
+  }
+}
+
+#[inline]
+fn seed_unsupported() -> Error {
+  "this format does not support deserializing with a seed".into()
+}
+
+#[inline]
+fn not_visited() -> Error {
+  "the format returned without deserializing a value".into()
+}
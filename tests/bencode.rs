@@ -0,0 +1,32 @@
+#![cfg(feature = "bencode")]
+
+use serde::{Deserialize, Serialize};
+use serde_multi::formats::bencode;
+use serde_multi::{multi, Format};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Torrent {
+  zeta: u32,
+  alpha: String,
+  beta: Vec<u8>,
+  #[serde(rename = "Upper")]
+  upper: u8
+}
+
+#[test]
+fn sorted_map_keys() {
+  let value = vec![("b", 4), ("ab", 3), ("a", 2), ("B", 1)].into_iter().collect::<HashMap<_, _>>();
+  let data = bencode::to_vec(&value).unwrap();
+  assert_eq!(data, b"d1:Bi1e1:ai2e2:abi3e1:bi4ee");
+  assert_eq!(multi::to_vec(Format::Bencode, &value).unwrap(), data);
+  assert_eq!(bencode::from_slice::<HashMap<String, i32>>(&data).unwrap().len(), 4);
+}
+
+#[test]
+fn sorted_struct_fields() {
+  let value = Torrent { zeta: 1, alpha: "a".to_owned(), beta: vec![2, 3], upper: 1 };
+  let data = bencode::to_vec(&value).unwrap();
+  assert_eq!(data, b"d5:Upperi1e5:alpha1:a4:betali2ei3ee4:zetai1ee");
+  assert_eq!(multi::from_slice::<Torrent>(Format::Bencode, &data).unwrap(), value);
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "bincode")]
+
+use serde_multi::formats::bincode::{Bincode, IntEncoding, TrailingBytes};
+use serde_multi::multi::{self, Configured};
+use serde_multi::{Format, SerdeBytes, SerdeStream};
+
+#[test]
+fn limit_applies_to_slices_and_readers() {
+  let data = Bincode::new().to_vec(&"x".repeat(100)).unwrap();
+  for trailing_bytes in [TrailingBytes::Allow, TrailingBytes::Reject] {
+    let bincode = Bincode::new().with_limit(10).with_trailing_bytes(trailing_bytes);
+    assert!(bincode.from_slice::<String>(&data).is_err());
+    assert!(bincode.from_reader::<_, String>(&data[..]).is_err());
+  }
+
+  assert_eq!(Bincode::new().with_limit(200).from_slice::<&str>(&data).unwrap().len(), 100);
+}
+
+#[test]
+fn limit_ignores_trailing_bytes() {
+  let mut data = Bincode::new().to_vec(&7u32).unwrap();
+  data.extend_from_slice(&[0; 20]);
+  assert_eq!(Bincode::new().with_limit(4).from_slice::<u32>(&data).unwrap(), 7);
+}
+
+#[test]
+fn options_through_multi() {
+  let bincode = Bincode::new().with_int_encoding(IntEncoding::Varint);
+  let data = multi::to_vec(bincode, &300u64).unwrap();
+  assert_eq!(data, bincode.to_vec(&300u64).unwrap());
+  assert_eq!(data.len(), 3);
+  assert_eq!(multi::to_vec(Format::Bincode, &300u64).unwrap().len(), 8);
+
+  let configured = Configured::from(bincode);
+  assert_eq!(configured.format(), Format::Bincode);
+  assert_eq!(multi::from_slice::<u64>(configured.clone(), &data).unwrap(), 300);
+  assert_eq!(configured.as_dyn_bytes().to_vec(&300u64).unwrap(), data);
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "bson")]
+
+use serde::{Deserialize, Serialize};
+use serde_bson::spec::ElementType;
+use serde_multi::formats::bson::NotADocument;
+use serde_multi::{multi, Format, FormatError};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Document {
+  name: String,
+  values: Vec<i32>
+}
+
+fn not_a_document(error: FormatError) -> NotADocument {
+  match error {
+    FormatError::Error(error) => *error.downcast::<NotADocument>().expect("not a NotADocument error"),
+    FormatError::Unsupported(unsupported) => panic!("unexpected {:?}", unsupported)
+  }
+}
+
+#[test]
+fn top_level_documents() {
+  let value = Document { name: "bson".to_owned(), values: vec![1, -2] };
+  let data = multi::to_vec(Format::Bson, &value).unwrap();
+  assert_eq!(multi::from_slice::<Document>(Format::Bson, &data).unwrap(), value);
+  assert_eq!(multi::from_reader::<_, Document>(Format::Bson, &data[..]).unwrap(), value);
+}
+
+#[test]
+fn not_a_document_through_multi() {
+  let error = not_a_document(multi::to_vec(Format::Bson, &5i32).unwrap_err());
+  assert_eq!(error.found(), ElementType::Int32);
+  assert_eq!(error.to_string(), "BSON can only serialize a document at the top level, found Int32");
+
+  let error = not_a_document(multi::to_vec(Format::Bson, &vec!["a", "b"]).unwrap_err());
+  assert_eq!(error.found(), ElementType::Array);
+
+  let error = not_a_document(multi::to_writer(Format::Bson, Vec::new(), &"text").unwrap_err());
+  assert_eq!(error.found(), ElementType::String);
+}
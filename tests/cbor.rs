@@ -0,0 +1,81 @@
+#![cfg(feature = "cbor")]
+
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_multi::formats::cbor::Cbor;
+use serde_multi::{multi, Format};
+
+/// A map whose keys are of different types and lengths, written out of order.
+struct Mixed;
+
+impl Serialize for Mixed {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where S: Serializer {
+    let mut map = serializer.serialize_map(Some(6))?;
+    map.serialize_entry("bb", &5)?;
+    map.serialize_entry(&-1, &2)?;
+    map.serialize_entry("z", &4)?;
+    map.serialize_entry(&1000, &1)?;
+    map.serialize_entry(&10, &0)?;
+    map.serialize_entry("a", &3)?;
+    map.end()
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Unsorted {
+  zz: u8,
+  b: Vec<Inner>
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Inner {
+  long: u8,
+  s: u8
+}
+
+#[test]
+fn canonical_key_order() {
+  let data = multi::to_vec(Cbor::new().with_canonical(true), &Mixed).unwrap();
+  assert_eq!(data, [
+    0xa6,
+    0x0a, 0x00,
+    0x19, 0x03, 0xe8, 0x01,
+    0x20, 0x02,
+    0x61, b'a', 0x03,
+    0x61, b'z', 0x04,
+    0x62, b'b', b'b', 0x05
+  ]);
+
+  // the default options keep the order the keys were written in
+  assert_eq!(multi::to_vec(Format::Cbor, &Mixed).unwrap()[1..4], [0x62, b'b', b'b']);
+}
+
+#[test]
+fn canonical_nested() {
+  let value = Unsorted { zz: 1, b: vec![Inner { long: 2, s: 3 }] };
+  let data = multi::to_vec(Cbor::new().with_canonical(true), &value).unwrap();
+  assert_eq!(data, [
+    0xa2,
+    0x61, b'b', 0x81, 0xa2, 0x61, b's', 0x03, 0x64, b'l', b'o', b'n', b'g', 0x02,
+    0x62, b'z', b'z', 0x01
+  ]);
+  assert_eq!(multi::from_slice::<Unsorted>(Format::Cbor, &data).unwrap(), value);
+
+  let mut written = Vec::new();
+  multi::to_writer(Cbor::new().with_canonical(true), &mut written, &value).unwrap();
+  assert_eq!(written, data);
+}
+
+#[test]
+fn packed_and_self_describe() {
+  let value = Inner { long: 2, s: 3 };
+  let data = multi::to_vec(Cbor::new().with_packed(true), &value).unwrap();
+  assert_eq!(data, serde_cbor::ser::to_vec_packed(&value).unwrap());
+  assert_eq!(multi::from_slice::<Inner>(Format::Cbor, &data).unwrap(), value);
+
+  let data = multi::to_vec(Cbor::new().with_self_describe(true), &value).unwrap();
+  assert_eq!(data[..3], [0xd9, 0xd9, 0xf7]);
+  assert_eq!(data[3..], serde_cbor::to_vec(&value).unwrap()[..]);
+  assert_eq!(multi::from_slice::<Inner>(Format::Cbor, &data).unwrap(), value);
+}
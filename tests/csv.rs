@@ -0,0 +1,121 @@
+#![cfg(feature = "csv")]
+
+use serde::{Deserialize, Serialize};
+use serde_multi::formats::csv::{self, Csv, Quoting};
+use serde_multi::{multi, Format, SerdeStream, SerdeText};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Row {
+  name: String,
+  count: u32,
+  ratio: Option<f64>
+}
+
+fn rows() -> Vec<Row> {
+  vec![
+    Row { name: "plain".to_owned(), count: 7, ratio: Some(0.5) },
+    Row { name: "with, comma".to_owned(), count: 0, ratio: None }
+  ]
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Flattened {
+  id: u32,
+  #[serde(flatten)]
+  extra: HashMap<String, String>
+}
+
+#[test]
+fn round_trip() {
+  let data = csv::to_string(&rows()).unwrap();
+  assert_eq!(data, "name,count,ratio\nplain,7,0.5\n\"with, comma\",0,\n");
+  assert_eq!(csv::from_str::<Vec<Row>>(&data).unwrap(), rows());
+  assert_eq!(multi::from_slice::<Vec<Row>>(Format::Csv, data.as_bytes()).unwrap(), rows());
+}
+
+#[test]
+fn untyped_fields_are_strings() {
+  let data = "id,code,value\n1,007,nan\n";
+  let records = csv::from_str::<Vec<Flattened>>(data).unwrap();
+  assert_eq!(records[0].id, 1);
+  assert_eq!(records[0].extra["code"], "007");
+  assert_eq!(records[0].extra["value"], "nan");
+
+  let records = csv::records::<_, Flattened>(data.as_bytes()).collect::<Result<Vec<_>, _>>().unwrap();
+  assert_eq!(records[0].extra["code"], "007");
+
+  #[cfg(feature = "json")]
+  {
+    let values = csv::from_str::<Vec<serde_json::Value>>(data).unwrap();
+    assert_eq!(values, vec![serde_json::json!({ "id": "1", "code": "007", "value": "nan" })]);
+  }
+
+  // typed fields are still parsed
+  let (code, _, value) = Csv::new().with_headers(false).from_str::<Vec<(u32, u32, f64)>>("007,1,nan\n").unwrap()[0];
+  assert_eq!(code, 7);
+  assert!(value.is_nan());
+}
+
+#[test]
+fn single_field_records() {
+  let data = Csv::new().with_headers(false).to_string(&vec![1, 2, 3]).unwrap();
+  assert_eq!(data, "1\n2\n3\n");
+  assert_eq!(Csv::new().with_headers(false).from_str::<Vec<u8>>(&data).unwrap(), vec![1, 2, 3]);
+  assert!(Csv::new().with_headers(false).from_str::<Vec<u8>>("1,2\n").is_err());
+}
+
+#[test]
+fn only_sequences_serialize() {
+  let error = csv::to_string(&rows()[0]).unwrap_err();
+  assert!(error.to_string().ends_with("CSV can only serialize a sequence of records"), "{}", error);
+  assert!(csv::to_string(&7).is_err());
+  assert_eq!(csv::to_string(&Some(rows())).unwrap(), csv::to_string(&rows()).unwrap());
+  assert_eq!(csv::to_string(&(Row { name: "a".to_owned(), count: 1, ratio: None },)).unwrap(), "name,count,ratio\na,1,\n");
+}
+
+#[test]
+fn errors_report_lines() {
+  let error = csv::from_str::<Vec<Row>>("name,count,ratio\na,1,\nb,x,\n").unwrap_err();
+  assert!(error.to_string().ends_with("at line 3"), "{}", error);
+}
+
+#[test]
+fn records() {
+  let data = "name,count,ratio\nplain,7,0.5\nbad,x,\n\"with, comma\",0,\n";
+  let mut records = csv::records::<_, Row>(data.as_bytes());
+  assert_eq!(records.next().unwrap().unwrap(), rows()[0]);
+  assert!(records.next().unwrap().is_err());
+  assert_eq!(records.next().unwrap().unwrap(), rows()[1]);
+  assert!(records.next().is_none());
+  assert!(records.next().is_none());
+
+  let records = Csv::new().with_headers(false).records::<_, (String, u32)>(&b"a,1\nb,2\n"[..]);
+  assert_eq!(records.collect::<Result<Vec<_>, _>>().unwrap(), vec![("a".to_owned(), 1), ("b".to_owned(), 2)]);
+
+  assert!(csv::records::<_, Row>(&b""[..]).next().is_none());
+}
+
+#[test]
+fn options() {
+  let tabs = Csv::new().with_delimiter(b'\t');
+  let data = tabs.to_string(&rows()).unwrap();
+  assert_eq!(data, "name\tcount\tratio\nplain\t7\t0.5\nwith, comma\t0\t\n");
+  assert_eq!(tabs.from_str::<Vec<Row>>(&data).unwrap(), rows());
+
+  let headerless = Csv::new().with_headers(false);
+  let data = headerless.to_string(&rows()).unwrap();
+  assert_eq!(data, "plain,7,0.5\n\"with, comma\",0,\n");
+  assert_eq!(headerless.from_str::<Vec<Row>>(&data).unwrap(), rows());
+
+  let always = Csv::new().with_quoting(Quoting::Always).to_string(&rows()).unwrap();
+  assert_eq!(always, "\"name\",\"count\",\"ratio\"\n\"plain\",\"7\",\"0.5\"\n\"with, comma\",\"0\",\"\"\n");
+  let non_numeric = Csv::new().with_quoting(Quoting::NonNumeric).to_string(&rows()).unwrap();
+  assert_eq!(non_numeric, "\"name\",\"count\",\"ratio\"\n\"plain\",7,0.5\n\"with, comma\",0,\"\"\n");
+  let never = Csv::new().with_quoting(Quoting::Never).to_string(&rows()).unwrap();
+  assert_eq!(never, "name,count,ratio\nplain,7,0.5\nwith, comma,0,\n");
+
+  let mut data = Vec::new();
+  multi::to_writer(tabs, &mut data, &rows()).unwrap();
+  assert_eq!(tabs.from_reader::<_, Vec<Row>>(&data[..]).unwrap(), rows());
+}
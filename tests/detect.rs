@@ -0,0 +1,34 @@
+#![cfg(all(feature = "json", feature = "cbor", feature = "messagepack"))]
+
+use serde_multi::multi::{detect_ranked, from_slice_auto};
+
+fn assert_text_first(data: &[u8]) {
+  let ranked = detect_ranked(data);
+  let first_binary = ranked.iter().position(|(format, _)| format.is_binary()).unwrap_or(ranked.len());
+  assert!(ranked[..first_binary].iter().any(|(format, _)| format.is_text()), "{:?}", ranked);
+  assert!(ranked[first_binary..].iter().all(|(format, _)| format.is_binary()), "{:?}", ranked);
+}
+
+#[test]
+fn short_number() {
+  assert_text_first(b"1");
+  assert_eq!(from_slice_auto::<i32>(b"1").unwrap(), 1);
+}
+
+#[test]
+fn short_string() {
+  assert_text_first(b"\"x\"");
+  assert_eq!(from_slice_auto::<String>(b"\"x\"").unwrap(), "x");
+}
+
+#[test]
+fn short_bool() {
+  assert_text_first(b"true");
+  assert!(from_slice_auto::<bool>(b"true").unwrap());
+}
+
+#[test]
+fn binary_data() {
+  let data = serde_multi::formats::cbor::to_vec(&vec![1, 2, 3]).unwrap();
+  assert_eq!(from_slice_auto::<Vec<i32>>(&data).unwrap(), vec![1, 2, 3]);
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "json")]
+
+use serde::{Deserialize, Serialize};
+use serde_multi::formats::json;
+use serde_multi::{DynSerdeBytes, DynSerdeStream, DynSerdeText, Error, Format, SerdeBytes};
+use std::marker::PhantomData;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Borrowed<'a> {
+  name: &'a str,
+  values: Vec<u32>
+}
+
+fn value() -> Borrowed<'static> {
+  Borrowed { name: "dyn", values: vec![1, 2] }
+}
+
+#[test]
+fn text() {
+  let text: &dyn DynSerdeText = Format::Json.as_dyn_text();
+  let data = text.to_string(&value()).unwrap();
+  assert_eq!(data, r#"{"name":"dyn","values":[1,2]}"#);
+  assert!(text.to_string_pretty(&value()).unwrap().contains('\n'));
+  assert_eq!(text.from_str::<Borrowed>(&data).unwrap(), value());
+  assert_eq!(text.from_str_seed(PhantomData::<Borrowed>, &data).unwrap(), value());
+}
+
+#[test]
+fn bytes() {
+  let bytes: &dyn DynSerdeBytes = Format::Json.as_dyn_bytes();
+  let data = bytes.to_vec(&value()).unwrap();
+  assert_eq!(data, br#"{"name":"dyn","values":[1,2]}"#);
+  assert!(bytes.to_vec_pretty(&value()).unwrap().contains(&b'\n'));
+  assert_eq!(bytes.from_slice::<Borrowed>(&data).unwrap(), value());
+  assert_eq!(bytes.from_slice_seed(PhantomData::<Borrowed>, &data).unwrap(), value());
+}
+
+#[test]
+fn stream() {
+  let stream: &dyn DynSerdeStream = Format::Json.as_dyn_stream();
+  let mut data = Vec::new();
+  stream.to_writer(&mut data, &vec![1, 2]).unwrap();
+  assert_eq!(data, b"[1,2]");
+  let mut pretty = Vec::new();
+  stream.to_writer_pretty(&mut pretty, &vec![1, 2]).unwrap();
+  assert!(pretty.contains(&b'\n'));
+  assert_eq!(stream.from_reader::<_, Vec<u32>>(&data[..]).unwrap(), vec![1, 2]);
+  assert_eq!(stream.from_reader_seed(PhantomData::<Vec<u32>>, &pretty[..]).unwrap(), vec![1, 2]);
+}
+
+/// A format that does not override `from_slice_seed`.
+struct Unseeded;
+
+impl SerdeBytes for Unseeded {
+  fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, Error>
+  where T: Serialize {
+    json::to_vec(value)
+  }
+
+  fn from_slice<'d, T>(&self, data: &'d [u8]) -> Result<T, Error>
+  where T: Deserialize<'d> {
+    json::from_slice(data)
+  }
+}
+
+#[test]
+fn without_seed_support() {
+  let bytes: &dyn DynSerdeBytes = &Unseeded;
+  let data = bytes.to_vec(&true).unwrap();
+  assert_eq!(data, b"true");
+  assert!(bytes.from_slice::<bool>(&data).is_err());
+  assert!(bytes.from_slice_seed(PhantomData::<bool>, &data).is_err());
+}
@@ -0,0 +1,60 @@
+use serde::de::IgnoredAny;
+use serde_multi::multi::{self, Feature};
+use serde_multi::{Format, FormatError};
+use std::collections::{BTreeMap, HashSet};
+use std::marker::PhantomData;
+
+/// The feature a `multi` function reported as unsupported, if any.
+fn unsupported<T>(result: Result<T, FormatError>) -> Option<Feature> {
+  match result {
+    Err(FormatError::Unsupported(unsupported)) => Some(unsupported.feature),
+    _ => None
+  }
+}
+
+#[test]
+fn supports_matches_multi() {
+  let value = vec![("key", "value")].into_iter().collect::<BTreeMap<_, _>>();
+  for format in Format::iter() {
+    let expected = |feature| Some(feature).filter(|&feature| !format.supports(feature));
+    let message = format!("{:?}", format);
+
+    assert_eq!(unsupported(multi::to_string_pretty(format, &value)), expected(Feature::Pretty), "{}", message);
+    assert_eq!(unsupported(multi::to_string(format, &value)), expected(Feature::Text), "{}", message);
+    assert_eq!(unsupported(multi::from_str::<IgnoredAny>(format, "")), expected(Feature::Text), "{}", message);
+    assert_eq!(unsupported(multi::from_str_seed(format, PhantomData::<IgnoredAny>, "")), expected(Feature::Text), "{}", message);
+
+    assert_eq!(unsupported(multi::to_vec_pretty(format, &value)), expected(Feature::Pretty), "{}", message);
+    assert_eq!(unsupported(multi::to_vec(format, &value)), expected(Feature::Bytes), "{}", message);
+    assert_eq!(unsupported(multi::from_slice::<IgnoredAny>(format, b"")), expected(Feature::Bytes), "{}", message);
+    assert_eq!(unsupported(multi::from_slice_seed(format, PhantomData::<IgnoredAny>, b"")), expected(Feature::Bytes), "{}", message);
+
+    assert_eq!(unsupported(multi::to_writer_pretty(format, Vec::new(), &value)), expected(Feature::Pretty), "{}", message);
+    assert_eq!(unsupported(multi::to_writer(format, Vec::new(), &value)), expected(Feature::Stream), "{}", message);
+    assert_eq!(unsupported(multi::from_reader::<_, IgnoredAny>(format, &b""[..])), expected(Feature::Stream), "{}", message);
+    assert_eq!(unsupported(multi::from_reader_seed(format, PhantomData::<IgnoredAny>, &b""[..])), expected(Feature::Stream), "{}", message);
+
+    assert_eq!(multi::extension(format).err().map(|unsupported| unsupported.feature), expected(Feature::Extension), "{}", message);
+    let features = Feature::ALL.iter().copied().filter(|&feature| format.supports(feature)).collect();
+    assert_eq!(format.features(), features, "{}", message);
+  }
+}
+
+#[test]
+fn enabled_formats() {
+  assert_eq!(Format::iter().collect::<Vec<_>>(), Format::ALL);
+  assert_eq!(Format::enabled(), Format::ALL);
+  assert_eq!(Format::iter().collect::<HashSet<_>>().len(), Format::ALL.len());
+
+  let names = Format::iter().map(|format| format!("{:?}", format).to_lowercase()).collect::<Vec<_>>();
+  let mut sorted = names.clone();
+  sorted.sort();
+  assert_eq!(names, sorted);
+
+  #[cfg(feature = "json")]
+  assert!(Format::ALL.contains(&Format::Json));
+  #[cfg(feature = "yaml")]
+  assert!(Format::ALL.contains(&Format::Yaml));
+  #[cfg(feature = "sexpr")]
+  assert!(Format::ALL.contains(&Format::SExpr));
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "flexbuffers")]
+
+use serde::{Deserialize, Serialize};
+use serde_multi::formats::flexbuffers;
+use serde_multi::{multi, Format};
+use std::marker::PhantomData;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Borrowed<'a> {
+  #[serde(borrow)]
+  name: &'a str,
+  #[serde(borrow)]
+  tags: Vec<&'a str>,
+  count: u32
+}
+
+#[test]
+fn borrowed_strings() {
+  let value = Borrowed { name: "flex", tags: vec!["a", "bc"], count: 3 };
+  let data = multi::to_vec(Format::FlexBuffers, &value).unwrap();
+
+  let borrowed = multi::from_slice::<Borrowed>(Format::FlexBuffers, &data).unwrap();
+  assert_eq!(borrowed, value);
+  // the strings point into the buffer rather than being copied out of it
+  let range = data.as_ptr_range();
+  assert!(range.contains(&borrowed.name.as_ptr()));
+  assert!(borrowed.tags.iter().all(|tag| range.contains(&tag.as_ptr())));
+
+  let seeded = multi::from_slice_seed(Format::FlexBuffers, PhantomData::<Borrowed>, &data).unwrap();
+  assert_eq!(seeded, value);
+  assert_eq!(flexbuffers::from_slice::<Borrowed>(&data).unwrap(), value);
+}
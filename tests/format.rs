@@ -0,0 +1,100 @@
+#![cfg(feature = "json")]
+
+use serde_multi::multi::ParseFormatErrorKind;
+use serde_multi::Format;
+use std::collections::HashMap;
+
+#[test]
+fn serde_round_trip() {
+  for &format in Format::ALL {
+    let data = serde_json::to_string(&format).unwrap();
+    assert_eq!(serde_json::from_str::<Format>(&data).unwrap(), format, "{}", data);
+  }
+}
+
+#[test]
+fn map_keys() {
+  let names = Format::iter().map(|format| (format, format.name())).collect::<HashMap<_, _>>();
+  assert_eq!(names.len(), Format::ALL.len());
+  for &format in Format::ALL {
+    assert_eq!(names[&format], format.name());
+  }
+
+  let data = serde_json::to_string(&names).unwrap();
+  assert_eq!(serde_json::from_str::<HashMap<Format, String>>(&data).unwrap().len(), Format::ALL.len());
+}
+
+#[cfg(feature = "messagepack")]
+#[test]
+fn messagepack_alias() {
+  let format = serde_json::from_str::<Format>("\"msgpack\"").unwrap();
+  assert_eq!(format, Format::MessagePack);
+  assert_eq!(format, serde_json::from_str::<Format>("\"messagepack\"").unwrap());
+  assert_eq!(serde_json::to_string(&format).unwrap(), "\"messagepack\"");
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn yaml_alias() {
+  let format = serde_json::from_str::<Format>("\"yml\"").unwrap();
+  assert_eq!(format, Format::Yaml);
+  assert_eq!(format, serde_json::from_str::<Format>("\"yaml\"").unwrap());
+  assert_eq!(serde_json::to_string(&format).unwrap(), "\"yaml\"");
+}
+
+#[test]
+fn parse_names() {
+  assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+  assert_eq!(" JSON ".parse::<Format>().unwrap(), Format::Json);
+  for &format in Format::ALL {
+    let name = serde_json::to_value(format).unwrap();
+    let name = name.as_str().unwrap();
+    assert_eq!(name.parse::<Format>().unwrap(), format);
+    assert_eq!(name.to_uppercase().parse::<Format>().unwrap(), format);
+  }
+
+  #[cfg(feature = "messagepack")]
+  assert_eq!("MsgPack".parse::<Format>().unwrap(), Format::MessagePack);
+  #[cfg(feature = "yaml")]
+  assert_eq!("YML".parse::<Format>().unwrap(), Format::Yaml);
+
+  let error = "jsonc".parse::<Format>().unwrap_err();
+  assert_eq!(error.kind, ParseFormatErrorKind::Name);
+  assert_eq!(error.input, "jsonc");
+  assert!(error.to_string().starts_with("unknown format name `jsonc`, expected one of: "), "{}", error);
+  assert!(error.to_string().contains("json"), "{}", error);
+}
+
+#[test]
+fn parse_extensions() {
+  assert_eq!(Format::from_extension("json").unwrap(), Format::Json);
+  assert_eq!(Format::from_extension(".JSON").unwrap(), Format::Json);
+  assert_eq!(Format::from_path("config/settings.json").unwrap(), Format::Json);
+  #[cfg(feature = "ron")]
+  assert_eq!(Format::from_path("settings.ron").unwrap(), Format::Ron);
+  #[cfg(feature = "yaml")]
+  assert_eq!(Format::from_extension("yml").unwrap(), Format::Yaml);
+
+  let error = Format::from_extension(".jsonc").unwrap_err();
+  assert_eq!(error.kind, ParseFormatErrorKind::Extension);
+  assert_eq!(error.input, "jsonc");
+  assert!(error.to_string().starts_with("unknown file extension `jsonc`"), "{}", error);
+
+  let error = Format::from_path("settings").unwrap_err();
+  assert_eq!(error.kind, ParseFormatErrorKind::Extension);
+  assert_eq!(error.input, "settings");
+  assert_eq!(Format::from_path("settings.jsonc").unwrap_err().input, "jsonc");
+}
+
+#[test]
+fn parse_mime_types() {
+  assert_eq!(Format::from_mime("application/json").unwrap(), Format::Json);
+  assert_eq!(Format::from_mime("Text/JSON; charset=utf-8").unwrap(), Format::Json);
+  #[cfg(feature = "cbor")]
+  assert_eq!(Format::from_mime("application/cbor").unwrap(), Format::Cbor);
+
+  let error = Format::from_mime("application/jsonc; charset=utf-8").unwrap_err();
+  assert_eq!(error.kind, ParseFormatErrorKind::MimeType);
+  assert_eq!(error.input, "application/jsonc");
+  assert!(error.to_string().starts_with("unknown MIME type `application/jsonc`"), "{}", error);
+}
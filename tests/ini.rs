@@ -0,0 +1,58 @@
+#![cfg(feature = "ini")]
+
+use serde::Deserialize;
+use serde_multi::formats::ini;
+use serde_multi::multi::{self, FormatError};
+use serde_multi::Format;
+use std::collections::{BTreeMap, HashMap};
+
+#[test]
+fn untyped_maps() {
+  let flat = ini::from_str::<BTreeMap<String, String>>("a=1\nb=2\n").unwrap();
+  assert_eq!(flat, vec![("a".to_owned(), "1".to_owned()), ("b".to_owned(), "2".to_owned())].into_iter().collect());
+
+  let sections = ini::from_str::<BTreeMap<String, BTreeMap<String, String>>>("[s]\nb=2\n").unwrap();
+  assert_eq!(sections["s"]["b"], "2");
+
+  assert!(ini::from_str::<BTreeMap<String, String>>("a=1\n[s]\nb=2\n").is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn untyped_values() {
+  let document = ini::from_str::<serde_json::Value>("a=007\n[s]\nb=true\nc=nan\n").unwrap();
+  assert_eq!(document, serde_json::json!({ "a": "007", "s": { "b": "true", "c": "nan" } }));
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Flattened {
+  id: u32,
+  #[serde(flatten)]
+  extra: HashMap<String, String>
+}
+
+#[test]
+fn flattened_maps() {
+  let document = ini::from_str::<Flattened>("id=1\ncode=007\nvalue=nan\n").unwrap();
+  assert_eq!(document.id, 1);
+  assert_eq!(document.extra["code"], "007");
+  assert_eq!(document.extra["value"], "nan");
+}
+
+#[test]
+fn nested_too_deeply() {
+  let value = vec![("a", vec![("b", vec![("c", 1)].into_iter().collect::<BTreeMap<_, _>>())].into_iter().collect::<BTreeMap<_, _>>())]
+    .into_iter()
+    .collect::<BTreeMap<_, _>>();
+  match multi::to_string(Format::Ini, &value) {
+    Err(FormatError::Error(error)) => {
+      assert_eq!(error.to_string(), "`a.b` is nested too deeply, INI can only group values into one level of sections");
+    },
+    result => panic!("unexpected result: {:?}", result)
+  }
+
+  match multi::to_string(Format::Ini, &vec![1]) {
+    Err(FormatError::Error(error)) => assert_eq!(error.to_string(), "INI can only serialize a map or struct at the top level"),
+    result => panic!("unexpected result: {:?}", result)
+  }
+}
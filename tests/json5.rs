@@ -0,0 +1,77 @@
+#![cfg(all(feature = "json", feature = "json5"))]
+
+use serde_multi::formats::json5::{self, Json5, Style};
+use serde_multi::SerdeText;
+use std::collections::BTreeMap;
+
+fn formats() -> Vec<Json5> {
+  vec![Json5::new(), Json5::new().with_style(Style::Json5)]
+}
+
+fn map(keys: &[&str]) -> BTreeMap<String, i32> {
+  keys.iter().enumerate().map(|(index, key)| (key.to_string(), index as i32)).collect()
+}
+
+/// Serializes a value in every style, checking that it reads back the same way,
+/// and that the plain JSON style is valid JSON.
+fn round_trip(value: &BTreeMap<String, i32>) -> Vec<String> {
+  let mut outputs = Vec::new();
+  for format in formats() {
+    for output in [format.to_string(value).unwrap(), format.to_string_pretty(value).unwrap()] {
+      assert_eq!(&json5::from_str::<BTreeMap<String, i32>>(&output).unwrap(), value, "{}", output);
+      if format.style() == Style::Json {
+        assert_eq!(&serde_json::from_str::<BTreeMap<String, i32>>(&output).unwrap(), value, "{}", output);
+      }
+
+      outputs.push(output);
+    }
+  }
+
+  outputs
+}
+
+#[test]
+fn escaped_keys() {
+  let outputs = round_trip(&map(&["a\"b", "c\\d", "e\\\"f", "g\nh"]));
+  assert_eq!(outputs[2], r#"{"a\"b":0,"c\\d":1,"e\\\"f":2,"g\nh":3}"#);
+}
+
+#[test]
+fn reserved_word_keys() {
+  let outputs = round_trip(&map(&["null", "true", "false", "NaN", "Infinity"]));
+  assert_eq!(outputs[0], r#"{"Infinity":4,"NaN":3,"false":2,"null":0,"true":1}"#);
+}
+
+#[test]
+fn empty_keys() {
+  let outputs = round_trip(&map(&["", "a"]));
+  assert_eq!(outputs[0], r#"{"":0,"a":1}"#);
+  assert_eq!(outputs[2], r#"{"":0,a:1}"#);
+  assert_eq!(outputs[3], "{\n  \"\": 0,\n  a: 1,\n}");
+}
+
+#[test]
+fn empty_containers() {
+  let value = (BTreeMap::<String, i32>::new(), Vec::<i32>::new(), vec![Vec::<i32>::new()]);
+  for format in formats() {
+    assert_eq!(format.to_string(&value).unwrap(), "[{},[],[[]]]");
+  }
+
+  assert_eq!(Json5::new().to_string_pretty(&value).unwrap(), "[\n  {},\n  [],\n  [\n    []\n  ]\n]");
+  assert_eq!(Json5::new().with_style(Style::Json5).to_string_pretty(&value).unwrap(), "[\n  {},\n  [],\n  [\n    [],\n  ],\n]");
+}
+
+#[test]
+fn non_finite_floats() {
+  let value = vec![f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1.5];
+  assert_eq!(Json5::new().to_string(&value).unwrap(), "[null,null,null,1.5]");
+  assert_eq!(Json5::new().to_string_pretty(&value).unwrap(), "[\n  null,\n  null,\n  null,\n  1.5\n]");
+
+  let json5 = Json5::new().with_style(Style::Json5);
+  assert_eq!(json5.to_string(&value).unwrap(), "[NaN,Infinity,-Infinity,1.5]");
+  assert_eq!(json5.to_string_pretty(&value).unwrap(), "[\n  NaN,\n  Infinity,\n  -Infinity,\n  1.5,\n]");
+
+  let read = json5::from_str::<Vec<f64>>(&json5.to_string(&value).unwrap()).unwrap();
+  assert!(read[0].is_nan());
+  assert_eq!(&read[1..], &value[1..]);
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "messagepack")]
+
+use serde::{Deserialize, Serialize, Serializer};
+use serde_multi::formats::messagepack::{BytesEncoding, MessagePack, StructEncoding, VariantEncoding};
+use serde_multi::{multi, Format, SerdeBytes};
+use std::collections::BTreeMap;
+
+/// A byte buffer that serializes as bytes, rather than as a sequence like `Vec<u8>`.
+#[derive(Clone)]
+struct Bytes(Vec<u8>);
+
+impl Serialize for Bytes {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where S: Serializer {
+    serializer.serialize_bytes(&self.0)
+  }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "_ExtStruct")]
+struct Ext((i8, Bytes));
+
+fn bytes(len: usize) -> Vec<u8> {
+  (0..len).map(|index| (index % 256) as u8).collect()
+}
+
+fn to_vec<T: Serialize>(value: &T) -> Vec<u8> {
+  MessagePack::new().with_bytes_encoding(BytesEncoding::Array).to_vec(value).unwrap()
+}
+
+#[test]
+fn lengths() {
+  for &len in &[0, 1, 255, 256, 65535, 65536, 70000] {
+    let data = bytes(len);
+    assert_eq!(to_vec(&Bytes(data.clone())), rmp_serde::to_vec(&data).unwrap(), "{}", len);
+  }
+}
+
+#[test]
+fn nested() {
+  let value = (vec![Bytes(bytes(3)), Bytes(bytes(300))], (Bytes(bytes(0)), "\u{c4}\u{c5}", 7u32));
+  let expected = (vec![bytes(3), bytes(300)], (bytes(0), "\u{c4}\u{c5}", 7u32));
+  assert_eq!(to_vec(&value), rmp_serde::to_vec(&expected).unwrap());
+}
+
+#[test]
+fn maps() {
+  let value = (0..20u8).map(|key| (vec![key], Bytes(bytes(key as usize * 20)))).collect::<BTreeMap<_, _>>();
+  let expected = (0..20u8).map(|key| (vec![key], bytes(key as usize * 20))).collect::<BTreeMap<_, _>>();
+  assert_eq!(to_vec(&value), rmp_serde::to_vec(&expected).unwrap());
+}
+
+#[test]
+fn ext_values_are_untouched() {
+  // ext payloads are opaque, so bytes inside them that look like `bin` markers must be left alone
+  let value = vec![Ext((1, Bytes(vec![0xc4]))), Ext((2, Bytes(vec![0xc4, 0x01, 0xff]))), Ext((3, Bytes(bytes(300))))];
+  let expected = rmp_serde::to_vec(&value).unwrap();
+  assert_eq!(to_vec(&value), expected);
+  assert_eq!(to_vec(&(Bytes(vec![1]), &value)), rmp_serde::to_vec(&(vec![1u8], &value)).unwrap());
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Point {
+  x: i32,
+  y: i32
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+  Dot(Point)
+}
+
+#[test]
+fn options_through_multi() {
+  let point = Point { x: 1, y: -2 };
+  let format = MessagePack::new().with_struct_encoding(StructEncoding::Map);
+  let data = multi::to_vec(format, &point).unwrap();
+  assert_eq!(data, rmp_serde::to_vec_named(&point).unwrap());
+  assert_eq!(multi::from_slice::<Point>(Format::MessagePack, &data).unwrap(), point);
+  assert_eq!(multi::from_slice::<Point>(format, &data).unwrap(), point);
+  assert_eq!(multi::from_reader::<_, Point>(format, &data[..]).unwrap(), point);
+
+  let mut data = Vec::new();
+  multi::to_writer(format, &mut data, &point).unwrap();
+  assert_eq!(data, rmp_serde::to_vec_named(&point).unwrap());
+
+  // the default options still write structs as arrays
+  assert_eq!(multi::to_vec(Format::MessagePack, &point).unwrap(), rmp_serde::to_vec(&point).unwrap());
+
+  let shape = Shape::Dot(point);
+  let format = format.with_variant_encoding(VariantEncoding::String);
+  let data = multi::to_vec(format, &shape).unwrap();
+  let mut expected = Vec::new();
+  shape.serialize(&mut rmp_serde::Serializer::new(&mut expected).with_struct_map().with_string_variants()).unwrap();
+  assert_eq!(data, expected);
+  assert_eq!(multi::from_slice::<Shape>(Format::MessagePack, &data).unwrap(), shape);
+}
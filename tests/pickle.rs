@@ -0,0 +1,64 @@
+#![cfg(feature = "pickle")]
+
+use serde::{Deserialize, Serialize};
+use serde_multi::formats::pickle::{self, Pickle, Protocol};
+use serde_multi::{multi, Format, SerdeBytes, SerdeStream};
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+  name: String,
+  data: Vec<u8>,
+  values: BTreeMap<String, (i64, f64)>,
+  missing: Option<bool>
+}
+
+fn record() -> Record {
+  Record {
+    name: "pickled".to_owned(),
+    data: vec![0, 1, 255],
+    values: vec![("a".to_owned(), (-1, 0.5)), ("b".to_owned(), (2, -1.5))].into_iter().collect(),
+    missing: None
+  }
+}
+
+#[test]
+fn protocol_headers() {
+  let data = Pickle::new().with_protocol(Protocol::V2).to_vec(&record()).unwrap();
+  assert!(data.starts_with(b"\x80\x02"));
+  assert_eq!(pickle::from_slice::<Record>(&data).unwrap(), record());
+
+  let data = Pickle::new().with_protocol(Protocol::V3).to_vec(&record()).unwrap();
+  assert!(data.starts_with(b"\x80\x03"));
+  assert_eq!(pickle::from_slice::<Record>(&data).unwrap(), record());
+
+  // the default options and the free functions write protocol 3
+  assert_eq!(Pickle::default().to_vec(&record()).unwrap(), data);
+  assert_eq!(pickle::to_vec(&record()).unwrap(), data);
+
+  let mut written = Vec::new();
+  multi::to_writer(Pickle::new().with_protocol(Protocol::V2), &mut written, &record()).unwrap();
+  assert!(written.starts_with(b"\x80\x02"));
+}
+
+#[test]
+fn round_trip_through_format() {
+  let data = multi::to_vec(Format::Pickle, &record()).unwrap();
+  assert!(data.starts_with(b"\x80\x03"));
+  assert_eq!(multi::from_slice::<Record>(Format::Pickle, &data).unwrap(), record());
+  assert_eq!(multi::from_slice_seed(Format::Pickle, PhantomData::<Record>, &data).unwrap(), record());
+  assert_eq!(multi::from_reader::<_, Record>(Format::Pickle, &data[..]).unwrap(), record());
+  assert_eq!(Format::Pickle.as_dyn_bytes().from_slice::<Record>(&data).unwrap(), record());
+}
+
+#[test]
+fn trailing_data() {
+  let mut data = pickle::to_vec(&record()).unwrap();
+  data.extend_from_slice(b"trailing");
+  assert!(pickle::from_slice_seed(PhantomData::<Record>, &data).is_err());
+  assert!(pickle::from_reader_seed(PhantomData::<Record>, &data[..]).is_err());
+  assert!(Pickle::new().from_reader_seed(PhantomData::<Record>, &data[..]).is_err());
+  assert!(multi::from_slice_seed(Format::Pickle, PhantomData::<Record>, &data).is_err());
+  assert!(Format::Pickle.as_dyn_bytes().from_slice::<Record>(&data).is_err());
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "plist")]
+
+use serde::{Deserialize, Serialize};
+use serde_multi::formats::plist::{self, Encoding, Plist};
+use serde_multi::{multi, Format, SerdeBytes, SerdeStream};
+use serde_plist::{Date, Dictionary, Uid, Value};
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+  Empty,
+  Circle(f64),
+  Point(i64, i64),
+  Rect { width: u32, height: u32 }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Document {
+  name: String,
+  missing: Option<u32>,
+  present: Option<u32>,
+  optional: Vec<Option<bool>>,
+  shapes: Vec<Shape>,
+  data: BTreeMap<String, i64>,
+  unit: (),
+  large: u64
+}
+
+fn document() -> Document {
+  Document {
+    name: "shapes".to_owned(),
+    missing: None,
+    present: Some(3),
+    optional: vec![Some(true), None],
+    shapes: vec![Shape::Empty, Shape::Circle(1.5), Shape::Point(-1, 2), Shape::Rect { width: 3, height: 4 }],
+    data: vec![("negative".to_owned(), -5), ("positive".to_owned(), 5)].into_iter().collect(),
+    unit: (),
+    large: u64::MAX
+  }
+}
+
+#[test]
+fn round_trip() {
+  for &encoding in &[Encoding::Xml, Encoding::Binary] {
+    let format = Plist::new().with_encoding(encoding);
+    let data = format.to_vec(&document()).unwrap();
+    assert_eq!(format.from_slice::<Document>(&data).unwrap(), document());
+    assert_eq!(format.from_reader::<_, Document>(&data[..]).unwrap(), document());
+    assert_eq!(Format::Plist.as_dyn_bytes().from_slice::<Document>(&data).unwrap(), document());
+  }
+}
+
+#[test]
+fn root_option() {
+  let data = plist::to_vec(&None::<u32>).unwrap();
+  assert_eq!(plist::from_slice::<Option<u32>>(&data).unwrap(), None);
+
+  let data = plist::to_vec(&Some(4u32)).unwrap();
+  assert_eq!(plist::from_slice::<Option<u32>>(&data).unwrap(), Some(4));
+}
+
+#[test]
+fn values() {
+  let data = b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<plist version=\"1.0\"><dict><key>date</key><date>2020-01-02T03:04:05Z</date><key>bytes</key><data>AAEC</data></dict></plist>";
+  let value = plist::from_slice::<Value>(data).unwrap();
+  let dictionary = value.as_dictionary().unwrap();
+  assert!(dictionary.get("date").unwrap().as_date().is_some());
+  assert_eq!(dictionary.get("bytes").unwrap().as_data(), Some(&[0, 1, 2][..]));
+}
+
+#[test]
+fn special_values_round_trip() {
+  let date = Date::from(SystemTime::UNIX_EPOCH + Duration::from_secs(1_577_934_245));
+  let mut dictionary = Dictionary::new();
+  dictionary.insert("date".to_owned(), Value::Date(date));
+  dictionary.insert("data".to_owned(), Value::Data(vec![0, 1, 2]));
+  dictionary.insert("dates".to_owned(), Value::Array(vec![Value::Date(date)]));
+  let xml = Value::Dictionary(dictionary.clone());
+
+  // only binary property lists can hold UIDs
+  dictionary.insert("uid".to_owned(), Value::Uid(Uid::new(7)));
+  let binary = Value::Dictionary(dictionary);
+
+  for (encoding, value) in &[(Encoding::Xml, xml), (Encoding::Binary, binary)] {
+    let data = multi::to_vec(Plist::new().with_encoding(*encoding), value).unwrap();
+    assert_eq!(&multi::from_slice::<Value>(Format::Plist, &data).unwrap(), value, "{:?}", encoding);
+  }
+}
+
+#[test]
+fn encoding_through_multi() {
+  let data = multi::to_vec(Plist::new().with_encoding(Encoding::Binary), &document()).unwrap();
+  assert!(data.starts_with(b"bplist00"));
+  assert_eq!(multi::from_slice::<Document>(Format::Plist, &data).unwrap(), document());
+
+  let mut written = Vec::new();
+  multi::to_writer(Plist::new().with_encoding(Encoding::Binary), &mut written, &document()).unwrap();
+  assert_eq!(written, data);
+
+  // the default options write XML
+  let data = multi::to_vec(Format::Plist, &document()).unwrap();
+  assert!(data.starts_with(b"<?xml"));
+}
+
+#[test]
+fn is_text() {
+  assert!(Format::Plist.is_text());
+  assert!(Plist::new().is_text());
+  assert!(!Plist::new().with_encoding(Encoding::Binary).is_text());
+
+  let data = Plist::new().with_encoding(Encoding::Binary).to_vec(&document()).unwrap();
+  assert!(data.starts_with(b"bplist00"));
+}
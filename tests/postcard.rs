@@ -0,0 +1,56 @@
+#![cfg(feature = "postcard")]
+
+use serde::{Deserialize, Serialize};
+use serde_multi::formats::postcard::{self, Postcard};
+use serde_multi::{SerdeBytes, SerdeStream};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Message {
+  id: u32,
+  name: String,
+  data: Vec<u8>
+}
+
+fn message(id: u32) -> Message {
+  Message { id, name: "zero\0byte".to_owned(), data: vec![0, 1, 0, 255] }
+}
+
+#[test]
+fn round_trip() {
+  let data = postcard::to_vec(&message(1)).unwrap();
+  assert_eq!(postcard::from_slice::<Message>(&data).unwrap(), message(1));
+  assert_eq!(postcard::from_reader::<_, Message>(&data[..]).unwrap(), message(1));
+
+  let data = postcard::to_vec(&"borrowed").unwrap();
+  assert_eq!(postcard::from_slice::<&str>(&data).unwrap(), "borrowed");
+}
+
+#[test]
+fn cobs_round_trip() {
+  let format = Postcard::new().with_cobs(true);
+  let mut data = Vec::new();
+  format.to_writer(&mut data, &message(1)).unwrap();
+  format.to_writer(&mut data, &message(2)).unwrap();
+  assert_eq!(data.iter().filter(|&&byte| byte == 0).count(), 2);
+
+  let mut reader = &data[..];
+  assert_eq!(format.from_reader::<_, Message>(&mut reader).unwrap(), message(1));
+  assert_eq!(format.from_reader::<_, Message>(&mut reader).unwrap(), message(2));
+  assert!(reader.is_empty());
+}
+
+#[test]
+fn cobs_rejects_truncated_frames() {
+  let format = Postcard::new().with_cobs(true);
+  let data = format.to_vec(&(1u8, 2u8)).unwrap();
+  assert_eq!(data.last(), Some(&0));
+  assert!(format.from_reader::<_, (u8, u8)>(&data[..data.len() - 1]).is_err());
+  assert!(format.from_reader::<_, (u8, u8)>(&[3, 1, 2][..]).is_err());
+}
+
+#[test]
+fn cobs_slices_are_rejected() {
+  let format = Postcard::new().with_cobs(true);
+  let data = format.to_vec(&(1u8, 2u8)).unwrap();
+  assert!(format.from_slice::<(u8, u8)>(&data).is_err());
+}
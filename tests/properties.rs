@@ -0,0 +1,81 @@
+#![cfg(feature = "properties")]
+
+use serde::{Deserialize, Serialize};
+use serde_multi::formats::properties;
+use serde_multi::multi::{self, FormatError};
+use serde_multi::Format;
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+  name: String,
+  server: Server
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Server {
+  host: String,
+  port: u16
+}
+
+#[test]
+fn escaped_round_trip() {
+  let entries = vec![
+    ("a=b", "c=d"),
+    ("a:b", "c:d"),
+    ("#comment", "#value"),
+    ("!bang", "!value"),
+    (" leading", " leading"),
+    ("trailing ", "trailing "),
+    ("inner space", "inner space"),
+    ("control\t\n\r\x0c\x01", "control\t\n\r\x0c\x01"),
+    ("back\\slash", "back\\slash"),
+    ("unicode \u{e9}", "\u{1f600}"),
+    ("", "")
+  ];
+  let map = entries.iter().map(|&(key, value)| (key.to_owned(), value.to_owned())).collect::<BTreeMap<_, _>>();
+
+  let data = properties::to_string(&map).unwrap();
+  assert_eq!(data.lines().count(), entries.len(), "{}", data);
+  assert_eq!(properties::from_str::<BTreeMap<String, String>>(&data).unwrap(), map);
+}
+
+#[test]
+fn escaping() {
+  let map = vec![("a=b c".to_owned(), " x=y\t".to_owned())].into_iter().collect::<BTreeMap<_, _>>();
+  assert_eq!(properties::to_string(&map).unwrap(), "a\\=b\\ c=\\ x=y\\t\n");
+}
+
+#[test]
+fn dotted_keys() {
+  let config = Config { name: "app".to_owned(), server: Server { host: "localhost".to_owned(), port: 8080 } };
+  let data = properties::to_string(&config).unwrap();
+  assert_eq!(data, "name=app\nserver.host=localhost\nserver.port=8080\n");
+  assert_eq!(properties::from_str::<Config>(&data).unwrap(), config);
+
+  // the keys can be in any order, and later duplicates replace earlier ones
+  let data = "server.port=1\nname=app\nserver.host=localhost\nserver.port=8080\n";
+  assert_eq!(properties::from_str::<Config>(data).unwrap(), config);
+
+  // maps without matching fields keep the dotted keys
+  let map = properties::from_str::<BTreeMap<String, String>>(data).unwrap();
+  assert_eq!(map["server.port"], "8080");
+}
+
+#[test]
+fn nested_too_deeply() {
+  let value = vec![("a", vec![("b", vec![("c", 1)].into_iter().collect::<BTreeMap<_, _>>())].into_iter().collect::<BTreeMap<_, _>>())]
+    .into_iter()
+    .collect::<BTreeMap<_, _>>();
+  match multi::to_string(Format::Properties, &value) {
+    Err(FormatError::Error(error)) => {
+      assert_eq!(error.to_string(), "`a.b` is nested too deeply, Java properties can only group values into one level of sections");
+    },
+    result => panic!("unexpected result: {:?}", result)
+  }
+
+  match multi::to_string(Format::Properties, &7) {
+    Err(FormatError::Error(error)) => assert_eq!(error.to_string(), "Java properties can only serialize a map or struct at the top level"),
+    result => panic!("unexpected result: {:?}", result)
+  }
+}
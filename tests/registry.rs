@@ -0,0 +1,48 @@
+#![cfg(feature = "json")]
+
+use serde_multi::formats::json::Json;
+use serde_multi::registry::{FormatEntry, FormatRegistry};
+
+#[test]
+fn dispatch_by_name() {
+  let registry = FormatRegistry::new();
+  let data = registry.to_string("json", &vec![1, 2]).unwrap();
+  assert_eq!(data, "[1,2]");
+  assert_eq!(registry.from_slice::<Vec<i32>>("JSON", data.as_bytes()).unwrap(), vec![1, 2]);
+  assert!(registry.to_vec("unknown", &1).is_err());
+}
+
+#[test]
+fn registered_format() {
+  let mut registry = FormatRegistry::empty();
+  registry.register(FormatEntry::new("config", Json::new()).with_stream(Json::new()));
+  let mut data = Vec::new();
+  registry.to_writer("config", &mut data, &true).unwrap();
+  assert!(registry.from_reader::<_, bool>("config", &data[..]).unwrap());
+  assert!(registry.to_string("config", &true).is_err());
+}
+
+#[test]
+fn builtin_aliases() {
+  let registry = FormatRegistry::new();
+  let entry = registry.get("Json").unwrap();
+  assert_eq!(entry.name(), "json");
+  assert_eq!(entry.aliases().count(), 0);
+  assert_eq!(registry.to_string("JSON", &1).unwrap(), "1");
+
+  #[cfg(feature = "messagepack")]
+  assert_eq!(registry.get("msgpack").unwrap().format(), Some(serde_multi::Format::MessagePack));
+  #[cfg(feature = "yaml")]
+  assert_eq!(registry.get("yml").unwrap().format(), Some(serde_multi::Format::Yaml));
+  #[cfg(feature = "sexpr")]
+  assert_eq!(registry.get("SEXP").unwrap().format(), Some(serde_multi::Format::SExpr));
+}
+
+#[test]
+fn registered_aliases() {
+  let mut registry = FormatRegistry::empty();
+  registry.register(FormatEntry::new("config", Json::new()).with_text(Json::new()).with_alias("Settings"));
+  assert_eq!(registry.get("settings").unwrap().name(), "config");
+  assert_eq!(registry.get("config").unwrap().aliases().collect::<Vec<_>>(), ["settings"]);
+  assert_eq!(registry.to_string("settings", &true).unwrap(), "true");
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "sexpr")]
+
+use serde::{Deserialize, Serialize};
+use serde_multi::formats::sexpr;
+use serde_multi::{multi, Format};
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+  Empty,
+  Circle(f64),
+  Point(i64, i64),
+  Rect { width: u32, height: u32 }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Inner {
+  label: String,
+  shape: Shape
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Document {
+  name: String,
+  missing: Option<u32>,
+  present: Option<Inner>,
+  inner: Vec<Inner>,
+  pair: (i32, String, bool),
+  nested: ((u8, u8), Vec<(char, i8)>),
+  shapes: Vec<Shape>,
+  data: BTreeMap<String, Vec<u32>>
+}
+
+fn document() -> Document {
+  Document {
+    name: "shapes".to_owned(),
+    missing: None,
+    present: Some(Inner { label: "some".to_owned(), shape: Shape::Point(-1, 2) }),
+    inner: vec![
+      Inner { label: "a".to_owned(), shape: Shape::Empty },
+      Inner { label: "b".to_owned(), shape: Shape::Rect { width: 3, height: 4 } }
+    ],
+    pair: (-7, "seven".to_owned(), true),
+    nested: ((1, 2), vec![('x', -1), ('y', 0)]),
+    shapes: vec![Shape::Empty, Shape::Circle(1.5), Shape::Point(-1, 2), Shape::Rect { width: 3, height: 4 }],
+    data: vec![("empty".to_owned(), vec![]), ("full".to_owned(), vec![1, 2, 3])].into_iter().collect()
+  }
+}
+
+#[test]
+fn pretty_round_trip() {
+  let data = sexpr::to_string_pretty(&document()).unwrap();
+  assert!(data.contains('\n'));
+  assert_eq!(sexpr::from_str::<Document>(&data).unwrap(), document());
+
+  let data = multi::to_string_pretty(Format::SExpr, &document()).unwrap();
+  assert_eq!(multi::from_str::<Document>(Format::SExpr, &data).unwrap(), document());
+}
+
+#[test]
+fn pretty_round_trip_values() {
+  let data = sexpr::to_string_pretty(&Some(Shape::Rect { width: 1, height: 2 })).unwrap();
+  assert_eq!(sexpr::from_str::<Option<Shape>>(&data).unwrap(), Some(Shape::Rect { width: 1, height: 2 }));
+
+  let data = sexpr::to_string_pretty(&None::<Shape>).unwrap();
+  assert_eq!(sexpr::from_str::<Option<Shape>>(&data).unwrap(), None);
+
+  let value = (Some((1u8, "one".to_owned())), vec![Some(Shape::Circle(0.5)), None]);
+  let data = sexpr::to_string_pretty(&value).unwrap();
+  assert_eq!(sexpr::from_str::<(Option<(u8, String)>, Vec<_>)>(&data).unwrap(), value);
+}
+
+#[test]
+fn pairs() {
+  assert_eq!(sexpr::from_str::<(i32, i32)>("(1 . 2)").unwrap(), (1, 2));
+  assert_eq!(sexpr::from_str::<Vec<i32>>("(1 . 2)").unwrap(), vec![1, 2]);
+  assert_eq!(sexpr::from_str::<Vec<i32>>("(1 2 3)").unwrap(), vec![1, 2, 3]);
+}
+
+#[test]
+fn improper_lists() {
+  let error = sexpr::from_str::<Vec<i32>>("(1 2 . 3)").unwrap_err();
+  assert!(error.to_string().contains("improper list"), "{}", error);
+  assert!(sexpr::from_str::<(i32, i32, i32)>("(1 2 . 3)").is_err());
+  assert!(sexpr::from_str::<(i32, i32)>("(1 2 . 3)").is_err());
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "urlencoded")]
+
+use serde::{Deserialize, Serialize};
+use serde_multi::{multi, Format};
+use std::marker::PhantomData;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Query {
+  search: String,
+  page: u32,
+  exact: bool,
+  filter: Option<String>
+}
+
+fn query() -> Query {
+  Query { search: "a&b = c/d ü".to_owned(), page: 2, exact: true, filter: None }
+}
+
+#[test]
+fn round_trip_through_format() {
+  let data = multi::to_string(Format::UrlEncoded, &query()).unwrap();
+  assert_eq!(data, "search=a%26b+%3D+c%2Fd+%C3%BC&page=2&exact=true");
+  assert_eq!(multi::from_str::<Query>(Format::UrlEncoded, &data).unwrap(), query());
+  assert_eq!(multi::from_str_seed(Format::UrlEncoded, PhantomData::<Query>, &data).unwrap(), query());
+
+  let bytes = multi::to_vec(Format::UrlEncoded, &query()).unwrap();
+  assert_eq!(bytes, data.as_bytes());
+  assert_eq!(multi::from_slice::<Query>(Format::UrlEncoded, &bytes).unwrap(), query());
+
+  let mut written = Vec::new();
+  multi::to_writer(Format::UrlEncoded, &mut written, &query()).unwrap();
+  assert_eq!(written, bytes);
+  assert_eq!(multi::from_reader::<_, Query>(Format::UrlEncoded, &written[..]).unwrap(), query());
+  assert_eq!(multi::from_reader_seed(Format::UrlEncoded, PhantomData::<Query>, &written[..]).unwrap(), query());
+
+  let filtered = Query { filter: Some("new".to_owned()), ..query() };
+  let data = multi::to_string(Format::UrlEncoded, &filtered).unwrap();
+  assert!(data.ends_with("&filter=new"), "{}", data);
+  assert_eq!(multi::from_str::<Query>(Format::UrlEncoded, &data).unwrap(), filtered);
+}
+
+#[test]
+fn nested_structs() {
+  #[derive(Serialize)]
+  struct Nested {
+    query: Query
+  }
+
+  assert!(multi::to_string(Format::UrlEncoded, &Nested { query: query() }).is_err());
+}
@@ -0,0 +1,18 @@
+#![cfg(feature = "xml")]
+
+use serde::Serialize;
+use serde_multi::formats::xml::Xml;
+use serde_multi::SerdeText;
+
+#[derive(Serialize)]
+struct Empty {
+  a: String
+}
+
+#[test]
+fn options_keep_empty_elements() {
+  let value = Empty { a: String::new() };
+  assert_eq!(Xml::new().to_string(&value).unwrap(), "<Empty><a></a></Empty>");
+  assert_eq!(Xml::new().with_root("r").to_string(&value).unwrap(), "<r><a></a></r>");
+  assert_eq!(Xml::new().to_string_pretty(&value).unwrap(), "<Empty>\n  <a></a>\n</Empty>");
+}
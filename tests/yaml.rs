@@ -0,0 +1,39 @@
+#![cfg(feature = "yaml")]
+
+use serde::{Deserialize, Serialize};
+use serde_multi::formats::yaml;
+use serde_multi::{multi, Format};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Document {
+  name: String,
+  values: Vec<u32>
+}
+
+const STREAM: &str = "name: first\nvalues: [1, 2]\n---\nname: second\nvalues: []\n---\nname: third\nvalues: [3]\n";
+
+fn documents() -> Vec<Document> {
+  vec![
+    Document { name: "first".to_owned(), values: vec![1, 2] },
+    Document { name: "second".to_owned(), values: vec![] },
+    Document { name: "third".to_owned(), values: vec![3] }
+  ]
+}
+
+#[test]
+fn multiple_documents() {
+  assert_eq!(yaml::from_str_multi::<Document>(STREAM).unwrap(), documents());
+  assert_eq!(yaml::from_slice_multi::<Document>(STREAM.as_bytes()).unwrap(), documents());
+  assert_eq!(yaml::from_reader_multi::<_, Document>(STREAM.as_bytes()).unwrap(), documents());
+
+  let single = "name: only\nvalues: [4]\n";
+  assert_eq!(yaml::from_str_multi::<Document>(single).unwrap(), vec![Document { name: "only".to_owned(), values: vec![4] }]);
+  assert!(yaml::from_str_multi::<Document>("name: first\nvalues: [1]\n---\nname: [oops]\n").is_err());
+}
+
+#[test]
+fn single_document_functions_reject_streams() {
+  assert!(yaml::from_str::<Document>(STREAM).is_err());
+  assert!(multi::from_str::<Document>(Format::Yaml, STREAM).is_err());
+  assert!(multi::from_reader::<_, Document>(Format::Yaml, STREAM.as_bytes()).is_err());
+}